@@ -0,0 +1,448 @@
+//! Synchronous Auth0 management client.
+//!
+//! [Auth0] mirrors [crate::Auth0] but every request blocks the current thread until it
+//! completes.  Requests are built with the same builders as the async client, wrapped in
+//! [Request] so they can be sent without an executor.
+//!
+//! ```no_run
+//! use auth0_management::blocking::Auth0;
+//! use auth0_management::Pageable;
+//!
+//! let auth0 = Auth0::builder()
+//!   .domain("example.auth0.com")
+//!   .client_id("CLIENT_ID")
+//!   .client_secret("CLIENT_SECRET")
+//!   .build_blocking()
+//!   .unwrap();
+//!
+//! let users = auth0.users.find().per_page(10).send().unwrap();
+//! ```
+//!
+//! The client runs its own runtime, it must not be used from within an async context.
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::{
+  Auth0Builder, Auth0Client, Auth0Request, Auth0RequestSimple, Auth0Result,
+  EmailVerificationTicketCreate, Page, PasswordChangeTicketCreate, RequestOptions, Role,
+  Sort, TokenInfo, User, UserBlock, UserBlocks, UserBlocksDelete,
+  UserBlocksDeleteByIdentifier, UserBlocksGet, UserBlocksGetByIdentifier, UserCreate,
+  UserDelete, UserEnrollment, UserEnrollmentsGet, UserGet, UserId, UserLogsGet,
+  UserMetadataPatch, UserRolesAssign, UserRolesGet, UserRolesRemove, UserUpdate,
+  UsersFind, UsersFindByEmail,
+};
+
+/// Synchronous Auth0 management client, reading and writing users with `A` app metadata and
+/// `U` user metadata.
+pub struct Auth0<A = Value, U = Value> {
+  /// Users manager
+  pub users: UsersManager<A, U>,
+  /// User blocks manager
+  pub user_blocks: UserBlocksManager,
+  /// Tickets manager
+  pub tickets: TicketsManager,
+
+  inner: crate::Auth0,
+  runtime: Runtime,
+}
+
+impl Auth0 {
+  /// Create synchronous management client from an async one.
+  pub fn new(inner: crate::Auth0) -> std::io::Result<Self> {
+    let runtime = Builder::new_multi_thread()
+      .worker_threads(1)
+      .thread_name("auth0-management")
+      .enable_all()
+      .build()?;
+    let handle = runtime.handle().clone();
+    let client = inner.client.clone();
+
+    Ok(Self {
+      users: UsersManager::new(client.clone(), handle.clone()),
+      user_blocks: UserBlocksManager::new(client.clone(), handle.clone()),
+      tickets: TicketsManager::new(client, handle),
+      inner,
+      runtime,
+    })
+  }
+
+  /// Create Auth0 client, finish with [Auth0Builder::build_blocking].
+  pub fn builder() -> Auth0Builder {
+    Default::default()
+  }
+}
+
+impl<A, U> Auth0<A, U> {
+  /// Bind the metadata types of the tenant, see [crate::Auth0::with_metadata].
+  pub fn with_metadata<A2, U2>(self) -> Auth0<A2, U2> {
+    Auth0 {
+      users: UsersManager::new(self.inner.client.clone(), self.runtime.handle().clone()),
+      user_blocks: self.user_blocks,
+      tickets: self.tickets,
+      inner: self.inner,
+      runtime: self.runtime,
+    }
+  }
+
+  /// Get scopes, expiry and claims of the current access token.
+  pub fn token_info(&self) -> Auth0Result<TokenInfo> {
+    self.runtime.handle().block_on(self.inner.token_info())
+  }
+}
+
+/// Request builder sent synchronously.
+///
+/// Dereferences to the wrapped builder, setters returning the builder are applied in
+/// statements before calling [Request::send].
+///
+/// ```no_run
+/// # use auth0_management::blocking::Auth0;
+/// # use auth0_management::UserId;
+/// # fn update(auth0: &Auth0) {
+/// let mut update = auth0.users.update(UserId::new("auth0|1"));
+/// update.email("test@example.test").blocked(false);
+///
+/// let user = update.send().unwrap();
+/// # }
+/// ```
+pub struct Request<'a, B> {
+  runtime: &'a Handle,
+  builder: B,
+}
+
+impl<'a, B> Request<'a, B> {
+  fn new(runtime: &'a Handle, builder: B) -> Self {
+    Self { runtime, builder }
+  }
+
+  /// Replace the wrapped builder, e.g. with one changing its metadata type.
+  pub fn map<C, F: FnOnce(B) -> C>(self, f: F) -> Request<'a, C> {
+    Request::new(self.runtime, f(self.builder))
+  }
+
+  /// Get the wrapped builder.
+  pub fn into_inner(self) -> B {
+    self.builder
+  }
+}
+
+impl<'a, B> Deref for Request<'a, B> {
+  type Target = B;
+
+  fn deref(&self) -> &B {
+    &self.builder
+  }
+}
+
+impl<'a, B> DerefMut for Request<'a, B> {
+  fn deref_mut(&mut self) -> &mut B {
+    &mut self.builder
+  }
+}
+
+impl<'a, B: AsMut<Page>> AsMut<Page> for Request<'a, B> {
+  fn as_mut(&mut self) -> &mut Page {
+    self.builder.as_mut()
+  }
+}
+
+impl<'a, F, B: AsMut<Sort<F>>> AsMut<Sort<F>> for Request<'a, B> {
+  fn as_mut(&mut self) -> &mut Sort<F> {
+    self.builder.as_mut()
+  }
+}
+
+impl<'a, B: AsMut<RequestOptions>> AsMut<RequestOptions> for Request<'a, B> {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    self.builder.as_mut()
+  }
+}
+
+impl<'a, A, U> Request<'a, UsersFind<A, U>>
+where
+  A: DeserializeOwned,
+  U: DeserializeOwned,
+{
+  /// Send
+  pub fn send(&self) -> Auth0Result<Vec<User<A, U>>> {
+    self.runtime.block_on(self.builder.send::<A, U>())
+  }
+}
+
+impl<'a, A, U> Request<'a, UsersFindByEmail<A, U>>
+where
+  A: DeserializeOwned,
+  U: DeserializeOwned,
+{
+  /// Send
+  pub fn send(&self) -> Auth0Result<Vec<User<A, U>>> {
+    self.runtime.block_on(self.builder.send::<A, U>())
+  }
+}
+
+impl<'a, A, U> Request<'a, UserCreate<A, U>>
+where
+  A: Serialize + DeserializeOwned,
+  U: Serialize + DeserializeOwned,
+{
+  /// Send
+  pub fn send(&self) -> Auth0Result<User<A, U>> {
+    self.runtime.block_on(self.builder.send::<A, U>())
+  }
+}
+
+impl<'a, A, U> Request<'a, UserUpdate<A, U>>
+where
+  A: Serialize + DeserializeOwned,
+  U: Serialize + DeserializeOwned,
+{
+  /// Send
+  pub fn send(&self) -> Auth0Result<User<A, U>> {
+    self.runtime.block_on(self.builder.send::<A, U>())
+  }
+}
+
+impl<'a, A, U> Request<'a, UserMetadataPatch<A, U>>
+where
+  A: DeserializeOwned,
+  U: DeserializeOwned,
+{
+  /// Send
+  pub fn send(&self) -> Auth0Result<User<A, U>> {
+    self.runtime.block_on(self.builder.send::<A, U>())
+  }
+}
+
+impl<'a> Request<'a, UserLogsGet> {
+  /// Send
+  pub fn send<T>(&self) -> Auth0Result<T>
+  where
+    T: DeserializeOwned + Send + Sync,
+  {
+    self.runtime.block_on(self.builder.send())
+  }
+}
+
+impl<'a> Request<'a, UserRolesGet> {
+  /// Send
+  pub fn send(&self) -> Auth0Result<Vec<Role>> {
+    self.runtime.block_on(self.builder.send())
+  }
+}
+
+impl<'a> Request<'a, UserRolesAssign> {
+  /// Send
+  pub fn send(&self) -> Auth0Result<()> {
+    self.runtime.block_on(self.builder.send())
+  }
+}
+
+impl<'a> Request<'a, UserRolesRemove> {
+  /// Send
+  pub fn send(&self) -> Auth0Result<()> {
+    self.runtime.block_on(self.builder.send())
+  }
+}
+
+impl<'a> Request<'a, EmailVerificationTicketCreate> {
+  /// Send
+  pub fn send(&self) -> Auth0Result<String> {
+    self.runtime.block_on(self.builder.send())
+  }
+}
+
+impl<'a> Request<'a, PasswordChangeTicketCreate> {
+  /// Send
+  pub fn send(&self) -> Auth0Result<String> {
+    self.runtime.block_on(self.builder.send())
+  }
+}
+
+/// Synchronous users manager, see [crate::UsersManager].
+pub struct UsersManager<A = Value, U = Value> {
+  client: Arc<Auth0Client>,
+  runtime: Handle,
+  metadata: PhantomData<fn() -> (A, U)>,
+}
+
+impl<A, U> UsersManager<A, U> {
+  fn new(client: Arc<Auth0Client>, runtime: Handle) -> Self {
+    Self {
+      client,
+      runtime,
+      metadata: PhantomData,
+    }
+  }
+
+  /// Create a user, see [crate::UsersManager::create].
+  pub fn create(&self) -> Request<'_, UserCreate<A, U>> {
+    Request::new(&self.runtime, UserCreate::typed(self.client.clone()))
+  }
+
+  /// Delete a user, see [crate::UsersManager::delete].
+  pub fn delete<I: Into<UserId>>(&self, id: I) -> Auth0Result<()> {
+    self
+      .runtime
+      .block_on(UserDelete::new(id).send_to(&self.client))
+  }
+
+  /// Retrieve the Guardian enrollments of a user, see
+  /// [crate::UsersManager::get_enrollments].
+  pub fn get_enrollments<I: Into<UserId>>(
+    &self,
+    id: I,
+  ) -> Auth0Result<Vec<UserEnrollment>> {
+    self
+      .runtime
+      .block_on(UserEnrollmentsGet::new(id).send_to(&self.client))
+  }
+
+  /// Retrieve user details, see [crate::UsersManager::get].
+  pub fn get<I: Into<UserId>>(&self, id: I) -> Auth0Result<User<A, U>>
+  where
+    A: DeserializeOwned + Send + Sync,
+    U: DeserializeOwned + Send + Sync,
+  {
+    self
+      .runtime
+      .block_on(UserGet::new(id).send_to(&self.client))
+  }
+
+  /// Retrieve log events of a user, see [crate::UsersManager::get_logs].
+  pub fn get_logs<I: Into<UserId>>(&self, id: I) -> Request<'_, UserLogsGet> {
+    Request::new(&self.runtime, UserLogsGet::new(self.client.clone(), id))
+  }
+
+  /// Retrieve the roles of a user, see [crate::UsersManager::get_roles].
+  pub fn get_roles<I: Into<UserId>>(&self, id: I) -> Request<'_, UserRolesGet> {
+    Request::new(&self.runtime, UserRolesGet::new(self.client.clone(), id))
+  }
+
+  /// Assign roles to a user, see [crate::UsersManager::assign_roles].
+  pub fn assign_roles<I: Into<UserId>>(&self, id: I) -> Request<'_, UserRolesAssign> {
+    Request::new(&self.runtime, UserRolesAssign::new(self.client.clone(), id))
+  }
+
+  /// Remove roles from a user, see [crate::UsersManager::remove_roles].
+  pub fn remove_roles<I: Into<UserId>>(&self, id: I) -> Request<'_, UserRolesRemove> {
+    Request::new(&self.runtime, UserRolesRemove::new(self.client.clone(), id))
+  }
+
+  /// Update a user, see [crate::UsersManager::update].
+  pub fn update<I: Into<UserId>>(&self, id: I) -> Request<'_, UserUpdate<A, U>> {
+    Request::new(&self.runtime, UserUpdate::typed(self.client.clone(), id))
+  }
+
+  /// Patch the metadata of a user, see [crate::UsersManager::patch_metadata].
+  pub fn patch_metadata<I: Into<UserId>>(
+    &self,
+    id: I,
+  ) -> Request<'_, UserMetadataPatch<A, U>> {
+    Request::new(
+      &self.runtime,
+      UserMetadataPatch::typed(self.client.clone(), id),
+    )
+  }
+
+  /// Retrieve details of users, see [crate::UsersManager::find].
+  pub fn find(&self) -> Request<'_, UsersFind<A, U>> {
+    Request::new(&self.runtime, UsersFind::typed(self.client.clone()))
+  }
+
+  /// Find users by email, see [crate::UsersManager::find_by_email].
+  pub fn find_by_email<S: AsRef<str>>(
+    &self,
+    email: S,
+  ) -> Request<'_, UsersFindByEmail<A, U>> {
+    Request::new(
+      &self.runtime,
+      UsersFindByEmail::typed(self.client.clone(), email),
+    )
+  }
+}
+
+/// Synchronous user blocks manager, see [crate::UserBlocksManager].
+pub struct UserBlocksManager {
+  client: Arc<Auth0Client>,
+  runtime: Handle,
+}
+
+impl UserBlocksManager {
+  fn new(client: Arc<Auth0Client>, runtime: Handle) -> Self {
+    Self { client, runtime }
+  }
+
+  /// Retrieve the blocks of a user, see [crate::UserBlocksManager::get].
+  pub fn get<I: Into<UserId>>(&self, id: I) -> Auth0Result<Vec<UserBlock>> {
+    let blocks: UserBlocks = self
+      .runtime
+      .block_on(UserBlocksGet::new(id).send_to(&self.client))?;
+
+    Ok(blocks.blocked_for)
+  }
+
+  /// Retrieve the blocks of an identifier, see
+  /// [crate::UserBlocksManager::get_by_identifier].
+  pub fn get_by_identifier<S: AsRef<str>>(
+    &self,
+    identifier: S,
+  ) -> Auth0Result<Vec<UserBlock>> {
+    let blocks: UserBlocks = self
+      .runtime
+      .block_on(UserBlocksGetByIdentifier::new(identifier).send_to(&self.client))?;
+
+    Ok(blocks.blocked_for)
+  }
+
+  /// Remove the blocks of a user, see [crate::UserBlocksManager::delete].
+  pub fn delete<I: Into<UserId>>(&self, id: I) -> Auth0Result<()> {
+    self
+      .runtime
+      .block_on(UserBlocksDelete::new(id).send_to(&self.client))
+  }
+
+  /// Remove the blocks of an identifier, see
+  /// [crate::UserBlocksManager::delete_by_identifier].
+  pub fn delete_by_identifier<S: AsRef<str>>(&self, identifier: S) -> Auth0Result<()> {
+    self
+      .runtime
+      .block_on(UserBlocksDeleteByIdentifier::new(identifier).send_to(&self.client))
+  }
+}
+
+/// Synchronous tickets manager, see [crate::TicketsManager].
+pub struct TicketsManager {
+  client: Arc<Auth0Client>,
+  runtime: Handle,
+}
+
+impl TicketsManager {
+  fn new(client: Arc<Auth0Client>, runtime: Handle) -> Self {
+    Self { client, runtime }
+  }
+
+  /// Create an email verification ticket, see [crate::TicketsManager::email_verification].
+  pub fn email_verification<I: Into<UserId>>(
+    &self,
+    user_id: I,
+  ) -> Request<'_, EmailVerificationTicketCreate> {
+    Request::new(
+      &self.runtime,
+      EmailVerificationTicketCreate::new(self.client.clone(), user_id),
+    )
+  }
+
+  /// Create a password change ticket, see [crate::TicketsManager::password_change].
+  pub fn password_change(&self) -> Request<'_, PasswordChangeTicketCreate> {
+    Request::new(
+      &self.runtime,
+      PasswordChangeTicketCreate::new(self.client.clone()),
+    )
+  }
+}
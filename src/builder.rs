@@ -1,19 +1,90 @@
 //! Builder for Auth0.
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
-use reqwest::Client;
+use reqwest::{Certificate, Client, Proxy};
+use serde::Deserialize;
 
+use crate::client::tenant_url;
+use crate::middleware::Middleware;
 use crate::rate::RateLimit;
-use crate::token::TokenManager;
-use crate::{Auth0, Auth0Client};
+use crate::token::{
+  ClientCredentials, PrivateKeyJwt, SigningAlgorithm, TokenCache, TokenManager,
+  TokenProvider,
+};
+use crate::{
+  Auth0, Auth0Client, Cassette, CassetteMode, HttpTransport, ReqwestTransport,
+};
 
 /// Management client interface.
+#[derive(Default)]
 pub struct Auth0Builder {
   domain: Option<String>,
   audience: Option<String>,
   client_id: Option<String>,
   client_secret: Option<String>,
+  private_key: Option<(String, String)>,
+  signing_algorithm: Option<SigningAlgorithm>,
+  token_provider: Option<Arc<dyn TokenProvider>>,
+  token_refresh_leeway: Option<Duration>,
+  token_cache: Option<Arc<dyn TokenCache>>,
+  http_client: Option<Client>,
+  transport: Option<Arc<dyn HttpTransport>>,
+  cassette: Option<(PathBuf, CassetteMode)>,
+  http: HttpOptions,
+  middleware: Vec<Arc<dyn Middleware>>,
+}
+
+/// Settings of the http client created by [Auth0Builder::build].
+#[derive(Default)]
+struct HttpOptions {
+  timeout: Option<Duration>,
+  connect_timeout: Option<Duration>,
+  proxies: Vec<Proxy>,
+  root_certificates: Vec<Certificate>,
+  user_agent: Option<String>,
+  pool_max_idle_per_host: Option<usize>,
+  pool_idle_timeout: Option<Duration>,
+  http2_prior_knowledge: bool,
+}
+
+impl HttpOptions {
+  fn build(self) -> reqwest::Result<Client> {
+    let mut builder = Client::builder().user_agent(
+      self
+        .user_agent
+        .as_deref()
+        .unwrap_or(concat!("auth0-management/", env!("CARGO_PKG_VERSION"))),
+    );
+
+    if let Some(timeout) = self.timeout {
+      builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = self.connect_timeout {
+      builder = builder.connect_timeout(timeout);
+    }
+    for proxy in self.proxies {
+      builder = builder.proxy(proxy);
+    }
+    for certificate in self.root_certificates {
+      builder = builder.add_root_certificate(certificate);
+    }
+    if let Some(max) = self.pool_max_idle_per_host {
+      builder = builder.pool_max_idle_per_host(max);
+    }
+    if let Some(timeout) = self.pool_idle_timeout {
+      builder = builder.pool_idle_timeout(timeout);
+    }
+    if self.http2_prior_knowledge {
+      builder = builder.http2_prior_knowledge();
+    }
+
+    builder.build()
+  }
 }
 
 impl Auth0Builder {
@@ -22,32 +93,170 @@ impl Auth0Builder {
     Default::default()
   }
 
+  /// Get builder populated from `AUTH0_` prefixed environment variables.
+  ///
+  /// See [Auth0Builder::from_env_prefixed].
+  pub fn from_env() -> Result<Self, Auth0BuilderError> {
+    Self::from_env_prefixed("AUTH0_")
+  }
+
+  /// Get builder populated from environment variables starting with `prefix`.
+  ///
+  /// The following variables are read, unset variables are left for the setters or
+  /// [Auth0Builder::build] to report.
+  /// * `{prefix}DOMAIN`
+  /// * `{prefix}AUDIENCE`
+  /// * `{prefix}CLIENT_ID`
+  /// * `{prefix}CLIENT_SECRET`
+  /// * `{prefix}PRIVATE_KEY` - PEM encoded private key.
+  /// * `{prefix}PRIVATE_KEY_PATH` - Path of PEM encoded private key.
+  /// * `{prefix}KEY_ID`
+  /// * `{prefix}SIGNING_ALGORITHM` - One of `RS256`, `PS256` or `ES256`.
+  pub fn from_env_prefixed(prefix: &str) -> Result<Self, Auth0BuilderError> {
+    let var = |name: &str| std::env::var(format!("{}{}", prefix, name)).ok();
+    let signing_algorithm = match var("SIGNING_ALGORITHM") {
+      Some(algorithm) => Some(serde_json::from_value(algorithm.clone().into()).map_err(
+        |_| {
+          Auth0BuilderError::Config(format!("unknown signing algorithm `{}`", algorithm))
+        },
+      )?),
+      None => None,
+    };
+
+    Profile {
+      domain: var("DOMAIN"),
+      audience: var("AUDIENCE"),
+      client_id: var("CLIENT_ID"),
+      client_secret: var("CLIENT_SECRET"),
+      private_key: var("PRIVATE_KEY"),
+      private_key_path: var("PRIVATE_KEY_PATH"),
+      key_id: var("KEY_ID"),
+      signing_algorithm,
+    }
+    .apply(Self::new())
+  }
+
+  /// Get builder populated from a named tenant profile of a configuration file.
+  ///
+  /// Files ending in `.json` are read as JSON, any other file as TOML.  Every top level table
+  /// is a profile with the same keys as [Auth0Builder::from_env_prefixed] in lower case.
+  ///
+  /// ```toml
+  /// [production]
+  /// domain = "example.auth0.com"
+  /// client_id = "CLIENT_ID"
+  /// client_secret = "CLIENT_SECRET"
+  ///
+  /// [staging]
+  /// domain = "example-staging.auth0.com"
+  /// client_id = "CLIENT_ID"
+  /// private_key_path = "/etc/auth0/staging.pem"
+  /// key_id = "KEY_ID"
+  /// ```
+  ///
+  /// # Arguments
+  /// * `path` - The path of the configuration file.
+  /// * `profile` - The name of the tenant profile.
+  pub fn from_config<P: AsRef<Path>>(
+    path: P,
+    profile: &str,
+  ) -> Result<Self, Auth0BuilderError> {
+    let path = path.as_ref();
+    let config = std::fs::read_to_string(path)
+      .map_err(|err| Auth0BuilderError::Config(format!("{}: {}", path.display(), err)))?;
+    let mut profiles: HashMap<String, Profile> =
+      if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&config).map_err(|err| err.to_string())
+      } else {
+        toml::from_str(&config).map_err(|err| err.to_string())
+      }
+      .map_err(|err| Auth0BuilderError::Config(format!("{}: {}", path.display(), err)))?;
+
+    profiles
+      .remove(profile)
+      .ok_or_else(|| Auth0BuilderError::MissingProfile(profile.to_owned()))?
+      .apply(Self::new())
+  }
+
   /// Get instance of management client.
   ///
   /// Creates instance of management client and validates builder options.  Valid builder options
-  /// requires all fields to be populated.
+  /// requires all fields to be populated, either [Auth0Builder::client_secret] or
+  /// [Auth0Builder::private_key] must be set to authenticate the application.  When a
+  /// [Auth0Builder::token_provider] is set only the domain is required.  The audience
+  /// defaults to the management API of the domain, `https://{domain}/api/v2/`.
   pub fn build(self) -> Result<Auth0, Auth0BuilderError> {
-    let client = Client::new();
+    let transport: Arc<dyn HttpTransport> = match (self.transport, self.http_client) {
+      (Some(transport), _) => transport,
+      (None, Some(client)) => Arc::new(ReqwestTransport::new(client)),
+      (None, None) => {
+        Arc::new(ReqwestTransport::new(self.http.build().map_err(|err| {
+          Auth0BuilderError::InvalidHttpClient(err.to_string())
+        })?))
+      }
+    };
+    let transport: Arc<dyn HttpTransport> = match self.cassette {
+      Some((path, mode)) => Arc::new(
+        Cassette::new(&path, mode, transport)
+          .map_err(|err| Auth0BuilderError::InvalidCassette(err.to_string()))?,
+      ),
+      None => transport,
+    };
     let domain = self.domain.ok_or(Auth0BuilderError::MissingDomain)?;
-    let audience = self.audience.ok_or(Auth0BuilderError::MissingAudience)?;
+    if let Some(token) = self.token_provider {
+      let client =
+        Auth0Client::with_transport(RateLimit::new(), token, transport, &domain);
+
+      return Ok(Auth0::new(with_middleware(client, self.middleware)));
+    }
+
+    let audience = self
+      .audience
+      .unwrap_or_else(|| tenant_url(&domain, "api/v2/"));
     let client_id = self.client_id.ok_or(Auth0BuilderError::MissingClientID)?;
-    let client_secret = self
-      .client_secret
-      .ok_or(Auth0BuilderError::MissingClientSecret)?;
-    let client = Auth0Client::new(
-      RateLimit::new(),
-      TokenManager::new(
-        client.clone(),
-        &domain,
-        &audience,
-        &client_id,
-        &client_secret,
+    let credentials = match (self.private_key, self.client_secret) {
+      (Some((_, key_id)), _) if key_id.is_empty() => {
+        return Err(Auth0BuilderError::MissingKeyId)
+      }
+      (Some((pem, key_id)), _) => ClientCredentials::PrivateKey(
+        PrivateKeyJwt::from_pem(
+          pem.as_bytes(),
+          &key_id,
+          self.signing_algorithm.unwrap_or(SigningAlgorithm::RS256),
+        )
+        .map_err(|_| Auth0BuilderError::InvalidPrivateKey)?,
       ),
-      client,
+      (None, Some(client_secret)) => ClientCredentials::Secret(client_secret),
+      (None, None) => return Err(Auth0BuilderError::MissingClientSecret),
+    };
+    let mut token = TokenManager::with_transport(
+      transport.clone(),
       &domain,
+      &audience,
+      &client_id,
+      credentials,
     );
+    if let Some(leeway) = self.token_refresh_leeway {
+      token = token.refresh_leeway(leeway);
+    }
+    if let Some(cache) = self.token_cache {
+      token = token.cache(cache);
+    }
+    for middleware in &self.middleware {
+      token = token.middleware(middleware.clone());
+    }
+
+    let client =
+      Auth0Client::with_transport(RateLimit::new(), Arc::new(token), transport, &domain);
 
-    Ok(Auth0::new(client))
+    Ok(Auth0::new(with_middleware(client, self.middleware)))
+  }
+
+  /// Build synchronous [crate::blocking::Auth0].
+  #[cfg(feature = "blocking")]
+  pub fn build_blocking(self) -> Result<crate::blocking::Auth0, Auth0BuilderError> {
+    crate::blocking::Auth0::new(self.build()?)
+      .map_err(|err| Auth0BuilderError::InvalidRuntime(err.to_string()))
   }
 
   /// The auth0 tenant domain.
@@ -64,7 +273,7 @@ impl Auth0Builder {
   ///
   /// The audience can be found in the Auth0 dashboard under you `API` settings.  Audience will be
   /// in the field labeled `Identifier`.  The default management API identifier will be formatted as
-  /// `https://example.eu.auth0.com/api/v2`
+  /// `https://example.eu.auth0.com/api/v2/` and is used when no audience is set.
   pub fn audience(mut self, audience: &str) -> Self {
     self.audience = Some(audience.to_owned());
     self
@@ -87,30 +296,218 @@ impl Auth0Builder {
     self.client_secret = Some(client_secret.to_owned());
     self
   }
-}
 
-impl Default for Auth0Builder {
-  fn default() -> Self {
-    Self {
-      domain: None,
-      audience: None,
-      client_id: None,
-      client_secret: None,
-    }
+  /// The private key used for `Private Key JWT` authentication of the Machine-to-Machine
+  /// application.
+  ///
+  /// Instead of sending the client secret, a client assertion signed with this key is sent
+  /// with every token request.  The matching public key must be registered under the
+  /// application `Credentials` settings, the key id will be listed alongside it.  Takes
+  /// precedence over [Auth0Builder::client_secret].
+  ///
+  /// # Arguments
+  /// * `pem` - PEM encoded RSA or EC private key.
+  /// * `key_id` - The id of the registered public key, sent as the `kid` header.
+  pub fn private_key(mut self, pem: &str, key_id: &str) -> Self {
+    self.private_key = Some((pem.to_owned(), key_id.to_owned()));
+    self
+  }
+
+  /// The algorithm used to sign client assertions with [Auth0Builder::private_key].
+  ///
+  /// Defaults to [SigningAlgorithm::RS256].
+  pub fn signing_algorithm(mut self, algorithm: SigningAlgorithm) -> Self {
+    self.signing_algorithm = Some(algorithm);
+    self
+  }
+
+  /// Time before expiration at which access tokens are refreshed.
+  ///
+  /// Defaults to one minute, the leeway is capped at half of the token lifetime.  See
+  /// [Auth0::token_refresh_task] to refresh tokens in the background.
+  pub fn token_refresh_leeway(mut self, leeway: Duration) -> Self {
+    self.token_refresh_leeway = Some(leeway);
+    self
+  }
+
+  /// The cache storing access tokens between token managers or process restarts.
+  ///
+  /// Tokens are cached by domain, audience and client id and reused while valid, which
+  /// saves Machine-to-Machine token requests for short-lived processes.  See
+  /// [FileTokenCache](crate::token::FileTokenCache) and
+  /// [MemoryTokenCache](crate::token::MemoryTokenCache).
+  pub fn token_cache<C: TokenCache + 'static>(mut self, cache: C) -> Self {
+    self.token_cache = Some(Arc::new(cache));
+    self
+  }
+
+  /// The provider of access tokens used to authenticate management API requests.
+  ///
+  /// Replaces the client credentials flow, [Auth0Builder::audience],
+  /// [Auth0Builder::client_id] and the application credentials are ignored.
+  pub fn token_provider<T: TokenProvider + 'static>(mut self, provider: T) -> Self {
+    self.token_provider = Some(Arc::new(provider));
+    self
+  }
+
+  /// Call `middleware` around every management API and token request, may be called
+  /// multiple times.
+  ///
+  /// Middleware sees the method, url, headers, status and timing of every request, see
+  /// [Middleware].
+  pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+    self.middleware.push(Arc::new(middleware));
+    self
+  }
+
+  /// The http client used for management API and token requests.
+  ///
+  /// Replaces the client created from the http settings of this builder, e.g.
+  /// [Auth0Builder::timeout] and [Auth0Builder::proxy] are ignored.
+  pub fn http_client(mut self, client: Client) -> Self {
+    self.http_client = Some(client);
+    self
+  }
+
+  /// The transport used for management API and token requests, see [HttpTransport].
+  ///
+  /// Replaces the reqwest client, the http settings of this builder and
+  /// [Auth0Builder::http_client] are ignored.
+  pub fn transport<T: HttpTransport + 'static>(mut self, transport: T) -> Self {
+    self.transport = Some(Arc::new(transport));
+    self
+  }
+
+  /// Record requests to the cassette at `path` or replay them from it, see [Cassette].
+  pub fn cassette<P: AsRef<Path>>(mut self, path: P, mode: CassetteMode) -> Self {
+    self.cassette = Some((path.as_ref().to_owned(), mode));
+    self
+  }
+
+  /// Time a request may take from connecting until the response body is read.
+  ///
+  /// Requests don't time out by default, individual requests may override the timeout.
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.http.timeout = Some(timeout);
+    self
+  }
+
+  /// Time connecting to the tenant may take.
+  pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+    self.http.connect_timeout = Some(timeout);
+    self
+  }
+
+  /// Proxy requests matching `proxy`, may be called multiple times.
+  pub fn proxy(mut self, proxy: Proxy) -> Self {
+    self.http.proxies.push(proxy);
+    self
+  }
+
+  /// Trust `certificate` in addition to the system root certificates, may be called
+  /// multiple times.
+  pub fn root_certificate(mut self, certificate: Certificate) -> Self {
+    self.http.root_certificates.push(certificate);
+    self
+  }
+
+  /// The `User-Agent` header sent with every request.
+  ///
+  /// Defaults to `auth0-management/{version}`.
+  pub fn user_agent(mut self, user_agent: &str) -> Self {
+    self.http.user_agent = Some(user_agent.to_owned());
+    self
+  }
+
+  /// Maximum number of idle connections kept open to the tenant.
+  pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+    self.http.pool_max_idle_per_host = Some(max);
+    self
+  }
+
+  /// Time idle connections are kept open.
+  pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+    self.http.pool_idle_timeout = Some(timeout);
+    self
+  }
+
+  /// Speak HTTP/2 without negotiating it first.
+  pub fn http2_prior_knowledge(mut self) -> Self {
+    self.http.http2_prior_knowledge = true;
+    self
   }
 }
 
+fn with_middleware(
+  client: Auth0Client,
+  middleware: Vec<Arc<dyn Middleware>>,
+) -> Auth0Client {
+  middleware.into_iter().fold(client, Auth0Client::middleware)
+}
+
 /// The error type which is returned from building a [Auth0].
 #[derive(Debug, PartialOrd, PartialEq)]
 pub enum Auth0BuilderError {
   /// Indicates builder didn't set [Auth0Builder::domain].
   MissingDomain,
-  /// Indicates builder didn't set [Auth0Builder::audience].
-  MissingAudience,
   /// Indicates builder didn't set [Auth0Builder::client_id].
   MissingClientID,
-  /// Indicates builder didn't set [Auth0Builder::client_secret].
+  /// Indicates builder didn't set [Auth0Builder::client_secret] or
+  /// [Auth0Builder::private_key].
   MissingClientSecret,
+  /// Indicates [Auth0Builder::private_key] isn't a valid key for the signing algorithm.
+  InvalidPrivateKey,
+  /// Indicates a private key was set without the id of the registered public key.
+  MissingKeyId,
+  /// Indicates the http client couldn't be created from the http settings.
+  InvalidHttpClient(String),
+  /// Indicates a configuration file or environment variable couldn't be read.
+  Config(String),
+  /// Indicates the configuration file has no profile with the given name.
+  MissingProfile(String),
+  /// Indicates the cassette couldn't be read.
+  InvalidCassette(String),
+  /// Indicates the runtime of the blocking client couldn't be started.
+  InvalidRuntime(String),
+}
+
+/// Tenant settings read from the environment or a configuration file.
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+  domain: Option<String>,
+  audience: Option<String>,
+  client_id: Option<String>,
+  client_secret: Option<String>,
+  private_key: Option<String>,
+  private_key_path: Option<String>,
+  key_id: Option<String>,
+  signing_algorithm: Option<SigningAlgorithm>,
+}
+
+impl Profile {
+  fn apply(self, mut builder: Auth0Builder) -> Result<Auth0Builder, Auth0BuilderError> {
+    builder.domain = self.domain.or(builder.domain);
+    builder.audience = self.audience.or(builder.audience);
+    builder.client_id = self.client_id.or(builder.client_id);
+    builder.client_secret = self.client_secret.or(builder.client_secret);
+    builder.signing_algorithm = self.signing_algorithm.or(builder.signing_algorithm);
+
+    let pem = match (self.private_key, self.private_key_path) {
+      (Some(pem), _) => Some(pem),
+      (None, Some(path)) => Some(
+        std::fs::read_to_string(&path)
+          .map_err(|err| Auth0BuilderError::Config(format!("{}: {}", path, err)))?,
+      ),
+      (None, None) => None,
+    };
+    if let Some(pem) = pem {
+      let key_id = self.key_id.ok_or(Auth0BuilderError::MissingKeyId)?;
+      builder.private_key = Some((pem, key_id));
+    }
+
+    Ok(builder)
+  }
 }
 
 impl Display for Auth0BuilderError {
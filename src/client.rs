@@ -1,71 +1,223 @@
 //! Auth0 request client.
-use reqwest::{Client, Method, RequestBuilder};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+
+use http::{Method, StatusCode};
+use reqwest::Client;
 use serde::de::DeserializeOwned;
 
+use crate::middleware::{execute, Middleware};
 use crate::rate::{RateLimit, RateLimitResponse};
-use crate::token::TokenManager;
-use crate::Auth0Error;
+use crate::token::{TokenInfo, TokenProvider};
+use crate::trace;
+use crate::ReqwestTransport;
+use crate::{Auth0Error, Endpoint, HttpError, HttpRequest, HttpResponse, HttpTransport};
 use crate::{Auth0ErrorResponse, Auth0Result};
 
 /// Auth0 management client.
-#[derive(Debug)]
 pub struct Auth0Client {
   rate: RateLimit,
-  token: TokenManager,
-  client: Client,
+  token: Arc<dyn TokenProvider>,
+  transport: Arc<dyn HttpTransport>,
   domain: String,
+  middleware: Vec<Arc<dyn Middleware>>,
 }
 
 impl Auth0Client {
   /// Create Auth0 client
-  pub fn new(rate: RateLimit, token: TokenManager, client: Client, domain: &str) -> Self {
+  pub fn new<T: TokenProvider + 'static>(
+    rate: RateLimit,
+    token: T,
+    client: Client,
+    domain: &str,
+  ) -> Self {
+    Self::with_token_provider(rate, Arc::new(token), client, domain)
+  }
+
+  /// Create Auth0 client using a shared token provider.
+  pub fn with_token_provider(
+    rate: RateLimit,
+    token: Arc<dyn TokenProvider>,
+    client: Client,
+    domain: &str,
+  ) -> Self {
+    Self::with_transport(rate, token, Arc::new(ReqwestTransport::new(client)), domain)
+  }
+
+  /// Create Auth0 client sending requests with `transport`.
+  pub fn with_transport(
+    rate: RateLimit,
+    token: Arc<dyn TokenProvider>,
+    transport: Arc<dyn HttpTransport>,
+    domain: &str,
+  ) -> Self {
     Self {
       rate,
       token,
-      client,
+      transport,
       domain: domain.to_owned(),
+      middleware: Vec::new(),
     }
   }
 
+  /// Call `middleware` around every request, see [Middleware].
+  pub fn middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+    self.middleware.push(middleware);
+    self
+  }
+
   /// Send request with auth0 client.
-  pub async fn send<R>(&self, req: RequestBuilder) -> Auth0Result<R>
+  ///
+  /// Requests wait while the rate limit of the tenant is exhausted, see
+  /// [RateLimit::acquire].  Requests rejected with `401 Unauthorized` are sent once more with
+  /// a new token.
+  pub async fn send<R>(&self, req: HttpRequest) -> Auth0Result<R>
+  where
+    R: DeserializeOwned,
+  {
+    trace::instrument_request(None, self.send_inner(req, &[])).await
+  }
+
+  /// Send request to `endpoint` with auth0 client.
+  ///
+  /// Fails with [Auth0Error::MissingScope] without sending the request when the access token
+  /// was granted none of the endpoint scopes.  Tokens which can't be decoded are sent as is.
+  ///
+  /// # Arguments
+  /// * `req` - The request to send.
+  /// * `endpoint` - The endpoint the request is sent to.
+  pub async fn send_endpoint<R>(
+    &self,
+    req: HttpRequest,
+    endpoint: &Endpoint,
+  ) -> Auth0Result<R>
   where
     R: DeserializeOwned,
   {
+    trace::instrument_request(Some(endpoint.path), self.send_inner(req, endpoint.scopes))
+      .await
+  }
+
+  async fn send_inner<R>(&self, req: HttpRequest, scopes: &[&str]) -> Auth0Result<R>
+  where
+    R: DeserializeOwned,
+  {
+    req.validate()?;
+    let retry = req.clone();
     let token = self.token.get_token().await?;
-    let res = req //
-      .bearer_auth(&token)
-      .send()
-      .await?;
-
-    if res.status().is_success() {
-      let body = res.rate_limit(&self.rate)?.bytes().await?;
-      let body = body.to_vec();
-      let body = std::str::from_utf8(&body).unwrap();
-      let body = if body.is_empty() { "null" } else { body };
-
-      Ok(serde_json::from_str::<R>(body)?)
+
+    if !scopes.is_empty() {
+      if let Ok(info) = TokenInfo::decode(&token) {
+        if !info.has_any_scope(scopes) {
+          return Err(Auth0Error::MissingScope(
+            scopes.iter().map(|scope| (*scope).to_owned()).collect(),
+          ));
+        }
+      }
+    }
+
+    trace::record_attempt(1);
+    let mut res = self.execute(req.bearer_auth(&token)).await?;
+
+    if res.status == StatusCode::UNAUTHORIZED {
+      trace::token_rejected();
+      self.token.invalidate(&token).await;
+      trace::record_attempt(2);
+
+      let token = self.token.get_token().await?;
+      res = self.execute(retry.bearer_auth(&token)).await?;
+    }
+
+    if res.status.is_success() {
+      let body = res.rate_limit(&self.rate)?.body;
+      let body: &[u8] = if body.is_empty() { b"null" } else { &body };
+
+      Ok(serde_json::from_slice::<R>(body)?)
     } else {
-      let body = res.bytes().await?;
-      let body = body.to_vec();
-      let body = std::str::from_utf8(&body).unwrap();
-
-      let err = serde_json::from_str::<Auth0ErrorResponse>(body);
-      if let Ok(err) = err {
-        Err(Auth0Error::from(err))
-      } else {
-        Err(Auth0Error::Auth0(body.to_owned()))
+      let body = res.body;
+
+      match serde_json::from_slice::<Auth0ErrorResponse>(&body) {
+        Ok(err) => Err(Auth0Error::from(err)),
+        Err(_) => Err(Auth0Error::Auth0(
+          String::from_utf8_lossy(&body).into_owned(),
+        )),
       }
     }
   }
 
-  /// Create auth0 request builder.
+  /// Send `req` once the rate limit allows it.
+  async fn execute(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+    self.rate.acquire().await;
+    let res = execute(&*self.transport, &self.middleware, req).await?;
+
+    if res.status == StatusCode::TOO_MANY_REQUESTS {
+      // Following requests wait for the reset announced with the rejection.
+      let _ = self.rate.read(&res.headers);
+    }
+
+    Ok(res)
+  }
+
+  /// Get scopes, expiry and claims of the current access token.
+  pub async fn token_info(&self) -> Auth0Result<TokenInfo> {
+    let token = self.token.get_token().await?;
+
+    Ok(TokenInfo::decode(&token)?)
+  }
+
+  /// Get future keeping the access token fresh in the background.
+  ///
+  /// The future never completes when the token provider refreshes ahead of expiration and
+  /// should be spawned on the runtime of the application.
+  pub fn token_refresh(&self) -> impl Future<Output = ()> + Send + 'static {
+    let token = self.token.clone();
+
+    async move { token.refresh().await }
+  }
+
+  /// Create auth0 request description for `endpoint` with the path parameters `args`.
+  ///
+  /// Requests with path parameters which can't be rendered fail when sent, see
+  /// [Endpoint::render].
+  pub fn begin_endpoint(
+    &self,
+    method: Method,
+    endpoint: &Endpoint,
+    args: &[&str],
+  ) -> HttpRequest {
+    match endpoint.render(args) {
+      Ok(path) => self.begin(method, &path),
+      Err(err) => self.begin(method, endpoint.path).error(err.to_string()),
+    }
+  }
+
+  /// Create auth0 request description.
   /// # Arguments
   /// * `method` = The HTTP request method.
   /// * `path` - The HTTP request path.
-  pub fn begin(&self, method: Method, path: &str) -> RequestBuilder {
-    self
-      .client
-      .request(method, &format!("https://{}/{}", self.domain, path))
+  pub fn begin(&self, method: Method, path: &str) -> HttpRequest {
+    HttpRequest::new(method, tenant_url(&self.domain, path))
+  }
+}
+
+impl Debug for Auth0Client {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Auth0Client")
+      .field("rate", &self.rate)
+      .field("domain", &self.domain)
+      .finish()
+  }
+}
+
+/// Get url of `path` on the tenant `domain`.
+///
+/// Domains are expected to be bare host names and are reached over `https`, a domain which
+/// already includes a scheme (e.g. `http://localhost:8080`) is used as is.
+pub(crate) fn tenant_url(domain: &str, path: &str) -> String {
+  if domain.starts_with("https://") || domain.starts_with("http://") {
+    format!("{}/{}", domain.trim_end_matches('/'), path)
+  } else {
+    format!("https://{}/{}", domain, path)
   }
 }
@@ -0,0 +1,82 @@
+//! Management API endpoints.
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// Characters escaped in path parameters, everything but unreserved characters.
+const PATH_PARAMETER: &AsciiSet = &NON_ALPHANUMERIC
+  .remove(b'-')
+  .remove(b'.')
+  .remove(b'_')
+  .remove(b'~');
+
+/// Management API endpoint a request is sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoint {
+  /// Path template relative to the tenant, parameters are named in braces, e.g.
+  /// `api/v2/users/{id}`.
+  pub path: &'static str,
+  /// Scopes of which any allows requests to the endpoint.
+  pub scopes: &'static [&'static str],
+}
+
+impl Endpoint {
+  /// Create endpoint.
+  ///
+  /// # Arguments
+  /// * `path` - The path template relative to the tenant.
+  /// * `scopes` - The scopes of which any allows requests, empty to skip the scope check.
+  pub const fn new(path: &'static str, scopes: &'static [&'static str]) -> Self {
+    Self { path, scopes }
+  }
+
+  /// Get path with the parameters of the template replaced by `args` in order.
+  ///
+  /// Arguments are percent-encoded so identifiers like `auth0|123` or ones containing
+  /// slashes always address a single path segment.  Empty arguments, `.` and `..` are
+  /// rejected, urls resolve them (even percent-encoded) to another endpoint.
+  pub fn render(&self, args: &[&str]) -> Result<String, EndpointError> {
+    let mut path = String::with_capacity(self.path.len());
+    let mut args = args.iter();
+    let mut rest = self.path;
+
+    while let Some(start) = rest.find('{') {
+      let end = start
+        + rest[start..]
+          .find('}')
+          .expect("unterminated path parameter");
+      let arg = args.next().expect("missing path parameter");
+
+      if arg.is_empty() || *arg == "." || *arg == ".." {
+        return Err(EndpointError::InvalidPathParameter((*arg).to_owned()));
+      }
+
+      path.push_str(&rest[..start]);
+      path.extend(utf8_percent_encode(arg, PATH_PARAMETER));
+      rest = &rest[end + 1..];
+    }
+
+    path.push_str(rest);
+    Ok(path)
+  }
+}
+
+/// Error rendering the path of an [Endpoint].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EndpointError {
+  /// Path parameter which is empty, `.` or `..` and doesn't address a single path segment.
+  InvalidPathParameter(String),
+}
+
+impl Display for EndpointError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      EndpointError::InvalidPathParameter(arg) => {
+        write!(f, "invalid path parameter {:?}", arg)
+      }
+    }
+  }
+}
+
+impl Error for EndpointError {}
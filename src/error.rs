@@ -5,6 +5,7 @@ use std::fmt::{Display, Formatter};
 
 use crate::rate::RateLimitError;
 use crate::token::TokenError;
+use crate::HttpError;
 use serde::Deserialize;
 
 /// Auth0 result type
@@ -16,13 +17,17 @@ pub enum Auth0Error {
   /// Json error
   Json(JsonError),
   /// Generic http error.
-  Http(reqwest::Error),
+  Http(HttpError),
   /// Authentication token error.
   Token(TokenError),
   /// Auth0 server side error.
   Auth0(String),
   /// Auth0 rate limit error.
   RateLimit(RateLimitError),
+  /// Concurrent writes kept overwriting changes to the user with the given id.
+  Conflict(String),
+  /// The access token was granted none of the scopes required by the request.
+  MissingScope(Vec<String>),
 }
 
 impl Display for Auth0Error {
@@ -45,8 +50,8 @@ impl From<TokenError> for Auth0Error {
   }
 }
 
-impl From<reqwest::Error> for Auth0Error {
-  fn from(inner: reqwest::Error) -> Self {
+impl From<HttpError> for Auth0Error {
+  fn from(inner: HttpError) -> Self {
     Auth0Error::Http(inner)
   }
 }
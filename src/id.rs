@@ -0,0 +1,167 @@
+//! Typed identifiers.
+//!
+//! Requests take the identifier type of the resource they address, so passing e.g. a
+//! connection ID where a user ID is expected doesn't compile.  Identifiers don't convert from
+//! strings implicitly, they are created with `new` or parsed with [str::parse].
+//!
+//! ```compile_fail
+//! # async fn get(auth0: auth0_management::Auth0) {
+//! // A plain string isn't a user ID.
+//! auth0.users.get("con_0000000000000001").await;
+//! # }
+//! ```
+//!
+//! ```compile_fail
+//! # use auth0_management::ConnectionId;
+//! # async fn get(auth0: auth0_management::Auth0) {
+//! auth0.users.get(ConnectionId::new("con_0000000000000001")).await;
+//! # }
+//! ```
+//!
+//! ```
+//! # use auth0_management::{User, UserId};
+//! # use serde_json::Value;
+//! # async fn get(auth0: auth0_management::Auth0) {
+//! let user: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+//! # }
+//! ```
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! id {
+  ($(#[$meta:meta])* $name:ident) => {
+    $(#[$meta])*
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct $name(String);
+
+    impl $name {
+      /// Create identifier.
+      pub fn new<S: Into<String>>(id: S) -> Self {
+        Self(id.into())
+      }
+
+      /// Get identifier as string slice.
+      pub fn as_str(&self) -> &str {
+        &self.0
+      }
+
+      /// Get identifier string.
+      pub fn into_string(self) -> String {
+        self.0
+      }
+    }
+
+    impl Display for $name {
+      fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+      }
+    }
+
+    impl FromStr for $name {
+      type Err = Infallible;
+
+      fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+      }
+    }
+
+    impl AsRef<str> for $name {
+      fn as_ref(&self) -> &str {
+        &self.0
+      }
+    }
+
+    impl From<&$name> for $name {
+      fn from(id: &$name) -> Self {
+        id.clone()
+      }
+    }
+
+    impl From<$name> for String {
+      fn from(id: $name) -> Self {
+        id.0
+      }
+    }
+
+    impl PartialEq<str> for $name {
+      fn eq(&self, other: &str) -> bool {
+        self.0 == other
+      }
+    }
+
+    impl PartialEq<&str> for $name {
+      fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+      }
+    }
+
+    impl PartialEq<String> for $name {
+      fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+      }
+    }
+  };
+}
+
+id!(
+  /// User ID, the identity provider and the user's ID at the provider separated by a pipe,
+  /// e.g. `auth0|5f7c8ec7c33c6c004bbafe82` or `google-oauth2|123`.
+  UserId
+);
+
+id!(
+  /// Role ID, e.g. `rol_0000000000000001`.
+  RoleId
+);
+
+id!(
+  /// Client (application) ID.
+  ClientId
+);
+
+id!(
+  /// Connection ID, e.g. `con_0000000000000001`.
+  ConnectionId
+);
+
+id!(
+  /// Organization ID, e.g. `org_0000000000000001`.
+  OrgId
+);
+
+id!(
+  /// Log event ID.
+  LogId
+);
+
+impl UserId {
+  /// Create user ID from the identity provider and the user's ID at the provider.
+  ///
+  /// # Arguments
+  /// * `provider` - The identity provider, e.g. `auth0` or `google-oauth2`.
+  /// * `id` - The ID of the user at the provider.
+  pub fn from_parts(provider: &str, id: &str) -> Self {
+    Self(format!("{}|{}", provider, id))
+  }
+
+  /// Split into the identity provider and the user's ID at the provider, `None` if the ID
+  /// has no provider prefix.
+  pub fn split(&self) -> Option<(&str, &str)> {
+    self.0.split_once('|')
+  }
+
+  /// Gets the identity provider, e.g. `auth0` for `auth0|123`.
+  pub fn provider(&self) -> Option<&str> {
+    self.split().map(|(provider, _)| provider)
+  }
+
+  /// Gets the user's ID at the identity provider, e.g. `123` for `auth0|123`.  The whole ID
+  /// is returned if it has no provider prefix.
+  pub fn provider_id(&self) -> &str {
+    self.split().map_or(&self.0, |(_, id)| id)
+  }
+}
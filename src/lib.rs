@@ -1,6 +1,29 @@
 #![warn(missing_docs)]
 //! Unofficial Auth0 Management API.
 //!
+//! # Features
+//! * `blocking` - Synchronous [blocking::Auth0] client over the same request builders.
+//! * `tracing` - Spans for every request with the method, path template, status, attempt
+//!   and remaining rate limit, and events for token refreshes and throttling.  Tokens,
+//!   secrets, ids and bodies are never recorded.
+//!
+//! # Runtime
+//! Requests are sent with [reqwest] by default and need a tokio 1.x runtime to drive the
+//! connections.  The client itself doesn't depend on a specific runtime: token refreshes,
+//! retries and waiting for the rate limit to reset use runtime-agnostic timers, and the
+//! futures returned by the client are `Send`.  Plug in another http client with an
+//! [HttpTransport] to run on a different executor.
+//!
+//! Request builders own a handle to the client, they are `Send + 'static` and can be stored,
+//! queued or moved to another task.  Awaiting a builder sends it.
+//!
+//! # Metadata
+//! Users are returned with `serde_json::Value` metadata unless the metadata types of the
+//! tenant are bound with [Auth0::with_metadata].  [UsersManager::get], [UsersManager::find],
+//! [UsersManager::create] and [UsersManager::update] then use those types.  The builders
+//! returned by `find`, `create` and `update` can still pick other types for a single request
+//! with `send::<A2, U2>()`, `get` always returns the bound types.
+//!
 //! # Connection Handling
 //! Authentication with Auth0 is handled for you provided you provide the values defined
 //! in the example below.  For additional information reference the official Auth0 guide
@@ -21,30 +44,28 @@
 //! # User Management
 //! ```
 //! use serde::{Serialize, Deserialize};
-//! use auth0_management::{Auth0, Pageable, Sortable, Ordering, Auth0Request, User};
+//! use auth0_management::{Auth0, Pageable, Sortable, Ordering, UserSortField};
 //!
-//! #[derive(Serialize, Deserialize)]
+//! #[derive(Serialize, Deserialize, Clone)]
 //! struct Metadata;
 //!
-//! async fn test_users(auth0: &Auth0) {
+//! async fn test_users(auth0: &Auth0<Metadata, Metadata>) {
 //!   // Create a user.
 //!   auth0
 //!     .users
 //!     .create()
 //!     .email("test@example.test")
 //!     .connection("CONNECTION_ID")
-//!     .send::<(), ()>()
 //!     .await
 //!     .expect("Failed to create a user.");
 //!
 //!   // Find first user user sort by email address.
-//!   let users: Vec<User<Metadata, Metadata>> = auth0
+//!   let users = auth0
 //!     .users
 //!     .find()
 //!     .page(0)
 //!     .per_page(1)
-//!     .sort("email", Ordering::Ascending)
-//!     .send()
+//!     .sort(UserSortField::Email, Ordering::Ascending)
 //!     .await
 //!     .expect("Failed to fetch users.");
 //!
@@ -58,7 +79,6 @@
 //!         .user_id
 //!     )
 //!     .email("test@test.test")
-//!     .send::<(), ()>()
 //!     .await
 //!     .expect("Failed to update user.");
 //! }
@@ -67,34 +87,64 @@
 pub use api::*;
 pub use builder::*;
 pub use client::*;
+pub use endpoint::*;
 pub use error::*;
+pub use id::*;
+pub use middleware::{Exchange, Middleware};
+pub use options::*;
 pub use page::*;
 pub use request::*;
 pub use sort::*;
+pub use tickets::*;
+pub use token::{
+  FileTokenCache, MemoryTokenCache, SigningAlgorithm, StaticToken, TokenCache, TokenInfo,
+  TokenProvider,
+};
+pub use transport::*;
+pub use user_blocks::*;
 pub use users::*;
 
-use crate::client::Auth0Client;
+use std::future::Future;
 use std::sync::Arc;
 
+use serde_json::Value;
+
 mod request;
 pub mod sort;
 
 #[allow(missing_docs)]
 pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod builder;
 pub mod client;
+pub mod endpoint;
 pub mod error;
+pub mod id;
+pub mod middleware;
+pub mod options;
 pub mod page;
 #[doc(hidden)]
 pub mod rate;
+pub mod tickets;
 #[doc(hidden)]
 pub mod token;
+mod trace;
+pub mod transport;
+pub mod user_blocks;
 pub mod users;
 
-/// Auth0 management client.
-pub struct Auth0 {
+/// Auth0 management client, users are read and written with `A` app metadata and `U` user
+/// metadata.  See [Auth0::with_metadata].
+pub struct Auth0<A = Value, U = Value> {
+  client: Arc<Auth0Client>,
+
   /// Users manager
-  pub users: UsersManager,
+  pub users: UsersManager<A, U>,
+  /// User blocks manager
+  pub user_blocks: UserBlocksManager,
+  /// Tickets manager
+  pub tickets: TicketsManager,
 }
 
 impl Auth0 {
@@ -103,7 +153,10 @@ impl Auth0 {
     let client = Arc::new(client);
 
     Self {
-      users: UsersManager::new(client),
+      users: UsersManager::new(client.clone()),
+      user_blocks: UserBlocksManager::new(client.clone()),
+      tickets: TicketsManager::new(client.clone()),
+      client,
     }
   }
 
@@ -112,3 +165,58 @@ impl Auth0 {
     Default::default()
   }
 }
+
+impl<A, U> Auth0<A, U> {
+  /// Bind the `app_metadata` and `user_metadata` types of the tenant, users are then
+  /// created, updated and returned with `A2` and `U2` metadata.
+  ///
+  /// ```
+  /// use serde::{Deserialize, Serialize};
+  /// use auth0_management::Auth0;
+  ///
+  /// #[derive(Serialize, Deserialize)]
+  /// struct AppMetadata {
+  ///   plan: String,
+  /// }
+  ///
+  /// #[derive(Serialize, Deserialize)]
+  /// struct UserMetadata {
+  ///   theme: String,
+  /// }
+  ///
+  /// fn init(auth0: Auth0) -> Auth0<AppMetadata, UserMetadata> {
+  ///   auth0.with_metadata()
+  /// }
+  /// ```
+  pub fn with_metadata<A2, U2>(self) -> Auth0<A2, U2> {
+    Auth0 {
+      users: UsersManager::new(self.client.clone()),
+      user_blocks: self.user_blocks,
+      tickets: self.tickets,
+      client: self.client,
+    }
+  }
+
+  /// Get background task refreshing the access token ahead of expiration.
+  ///
+  /// The task refreshes tokens at twice the refresh leeway before expiration, before
+  /// requests would, and requests keep using the current token while the new one is
+  /// fetched.  Requests only wait for a token if a refresh failed until the token is due.
+  /// The task never completes and should be spawned on the runtime of the application.
+  ///
+  /// ```
+  /// use auth0_management::Auth0;
+  ///
+  /// async fn init(auth0: &Auth0) {
+  ///   tokio::spawn(auth0.token_refresh_task());
+  /// }
+  /// ```
+  pub fn token_refresh_task(&self) -> impl Future<Output = ()> + Send + 'static {
+    self.client.token_refresh()
+  }
+
+  /// Get scopes, expiry and claims of the current access token.
+  pub async fn token_info(&self) -> Auth0Result<TokenInfo> {
+    self.client.token_info().await
+  }
+}
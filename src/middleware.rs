@@ -0,0 +1,116 @@
+//! Request and response hooks.
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use http::{HeaderMap, Method, StatusCode};
+
+use crate::trace;
+use crate::transport::redact::{redact_body, redact_headers, redact_response_body};
+use crate::{HttpBody, HttpError, HttpRequest, HttpResponse, HttpTransport};
+
+/// Hooks called around every request sent to the tenant, including token requests.
+///
+/// Middleware is called in the order it was added to [Auth0Builder](crate::Auth0Builder).
+///
+/// ```
+/// use auth0_management::{Auth0, Exchange, Middleware};
+///
+/// struct Log;
+///
+/// impl Middleware for Log {
+///   fn after_receive(&self, exchange: &Exchange<'_>) {
+///     println!("{} {:?} in {:?}", exchange.url, exchange.status, exchange.elapsed);
+///     if let Some(body) = exchange.response_body {
+///       println!("{}", String::from_utf8_lossy(body));
+///     }
+///   }
+/// }
+///
+/// let auth0 = Auth0::builder()
+///   .domain("example.auth0.com")
+///   .client_id("CLIENT_ID")
+///   .client_secret("CLIENT_SECRET")
+///   .middleware(Log)
+///   .build()
+///   .unwrap();
+/// ```
+pub trait Middleware: Send + Sync {
+  /// Called before `req` is sent, the request may be modified.
+  fn before_send(&self, _req: &mut HttpRequest) {}
+
+  /// Called once the response was received or the request failed.
+  fn after_receive(&self, _exchange: &Exchange<'_>) {}
+}
+
+/// Request and the outcome of sending it.
+///
+/// Secrets are redacted: the `authorization` and `set-cookie` headers as well as token,
+/// client secret and password fields of bodies are replaced with `[REDACTED]`.
+#[derive(Debug)]
+pub struct Exchange<'a> {
+  /// Method of the request.
+  pub method: &'a Method,
+  /// Url of the request without query.
+  pub url: &'a str,
+  /// Headers sent with the request.
+  pub request_headers: &'a HeaderMap,
+  /// Body sent with the request, `None` when the request had no body.
+  pub request_body: Option<&'a HttpBody>,
+  /// Status of the response, `None` when the request failed.
+  pub status: Option<StatusCode>,
+  /// Headers of the response, `None` when the request failed.
+  pub response_headers: Option<&'a HeaderMap>,
+  /// Body of the response, `None` when the request failed.
+  pub response_body: Option<&'a [u8]>,
+  /// Time from sending the request until the response was received or the request failed.
+  pub elapsed: Duration,
+}
+
+/// Send `req` with `transport`, calling `middleware` around it.
+pub(crate) async fn execute(
+  transport: &dyn HttpTransport,
+  middleware: &[Arc<dyn Middleware>],
+  mut req: HttpRequest,
+) -> Result<HttpResponse, HttpError> {
+  req.validate()?;
+  trace::record_request(&req);
+  if middleware.is_empty() {
+    let res = transport.send(req).await?;
+    trace::record_response(&res);
+
+    return Ok(res);
+  }
+
+  for middleware in middleware {
+    middleware.before_send(&mut req);
+  }
+
+  let method = req.method.clone();
+  let url = req.url.clone();
+  let request_headers = redact_headers(&req.headers);
+  let request_body = req.body.as_ref().map(redact_body);
+  let started = Instant::now();
+  let res = transport.send(req).await;
+  let elapsed = started.elapsed();
+  let response_headers = res.as_ref().ok().map(|res| redact_headers(&res.headers));
+  let response_body = res.as_ref().ok().map(|res| redact_response_body(&res.body));
+  let exchange = Exchange {
+    method: &method,
+    url: &url,
+    request_headers: &request_headers,
+    request_body: request_body.as_ref(),
+    status: res.as_ref().ok().map(|res| res.status),
+    response_headers: response_headers.as_ref(),
+    response_body: response_body.as_deref(),
+    elapsed,
+  };
+
+  for middleware in middleware {
+    middleware.after_receive(&exchange);
+  }
+  if let Ok(res) = &res {
+    trace::record_response(res);
+  }
+
+  res
+}
@@ -0,0 +1,38 @@
+//! Request options helper.
+use std::time::Duration;
+
+use crate::HttpRequest;
+
+/// Provides options applied to a single request.
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+  timeout: Option<Duration>,
+}
+
+impl RequestOptions {
+  /// Create request options instance.
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Apply options to `req`.
+  pub(crate) fn apply(&self, req: HttpRequest) -> HttpRequest {
+    match self.timeout {
+      Some(timeout) => req.timeout(timeout),
+      None => req,
+    }
+  }
+}
+
+/// Build request options.
+pub trait Configurable {
+  /// Time the request may take, overrides the timeout of the http client.
+  fn timeout(&mut self, timeout: Duration) -> &mut Self;
+}
+
+impl<O: AsMut<RequestOptions>> Configurable for O {
+  fn timeout(&mut self, timeout: Duration) -> &mut Self {
+    self.as_mut().timeout = Some(timeout);
+    self
+  }
+}
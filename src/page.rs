@@ -2,7 +2,7 @@
 use serde::Serialize;
 
 /// Provides serializable pagination parameters.
-#[derive(Serialize)]
+#[derive(Serialize, Clone, Default)]
 pub struct Page {
   #[serde(skip_serializing_if = "Option::is_none")]
   page: Option<u32>,
@@ -18,15 +18,6 @@ impl Page {
   }
 }
 
-impl Default for Page {
-  fn default() -> Self {
-    Self {
-      page: None,
-      per_page: None,
-    }
-  }
-}
-
 /// Build pagination parameters.
 pub trait Pageable {
   /// Page index of the results to return.  First page is `0`.
@@ -2,10 +2,12 @@ use std::error::Error;
 use std::num::ParseIntError;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use reqwest::header::ToStrError;
-use reqwest::Response;
-use serde::export::fmt::Display;
-use serde::export::Formatter;
+use futures_timer::Delay;
+use http::header::{HeaderMap, ToStrError};
+
+use crate::trace;
+use crate::HttpResponse;
+use std::fmt::{Display, Formatter};
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 /// Provides ability to read rate limit headers and check if limits are exceeded.
@@ -37,20 +39,38 @@ impl RateLimit {
         .store(self.limit.load(Ordering::SeqCst), Ordering::SeqCst);
     }
 
-    // TODO: Make this thread safe.
-    if self.remaining.load(Ordering::SeqCst) > 0 {
-      self.remaining.fetch_sub(1, Ordering::SeqCst);
+    self
+      .remaining
+      .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |remaining| {
+        remaining.checked_sub(1)
+      })
+      .is_ok()
+  }
 
-      true
-    } else {
-      false
+  /// Gets time to wait until the rate limit resets, `None` when a request may be sent right
+  /// away.  The request is counted against the limit when `None` is returned.
+  pub fn wait_time(&self) -> Option<Duration> {
+    if self.check_limit() {
+      return None;
     }
+
+    let expire = UNIX_EPOCH + Duration::from_secs(self.reset.load(Ordering::SeqCst));
+    expire.duration_since(SystemTime::now()).ok()
   }
 
-  /// Read response headers and updates limits.
-  pub fn read(&self, res: &Response) -> Result<(), RateLimitError> {
-    let headers = res.headers();
+  /// Waits until a request may be sent without exceeding the rate limit and counts it
+  /// against the limit.
+  ///
+  /// The wait uses a timer which doesn't depend on a specific async runtime.
+  pub async fn acquire(&self) {
+    while let Some(wait) = self.wait_time() {
+      trace::throttled(wait);
+      Delay::new(wait).await;
+    }
+  }
 
+  /// Read rate limit response headers and updates limits.
+  pub fn read(&self, headers: &HeaderMap) -> Result<(), RateLimitError> {
     self.limit.store(
       headers
         .get("x-ratelimit-limit")
@@ -86,9 +106,9 @@ pub trait RateLimitResponse: Sized {
   fn rate_limit(self, rate_limit: &RateLimit) -> Result<Self, RateLimitError>;
 }
 
-impl RateLimitResponse for Response {
+impl RateLimitResponse for HttpResponse {
   fn rate_limit(self, rate_limit: &RateLimit) -> Result<Self, RateLimitError> {
-    rate_limit.read(&self)?;
+    rate_limit.read(&self.headers)?;
     Ok(self)
   }
 }
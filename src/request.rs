@@ -1,8 +1,13 @@
+use std::future::Future;
+use std::pin::Pin;
+
 use async_trait::async_trait;
-use reqwest::RequestBuilder;
 use serde::de::DeserializeOwned;
 
-use crate::{Auth0Client, Auth0Result};
+use crate::{Auth0Client, Auth0Result, Endpoint, HttpRequest};
+
+/// Future of a request builder sent with `.await`, owns the builder so it can be spawned.
+pub type SendFuture<T> = Pin<Box<dyn Future<Output = Auth0Result<T>> + Send + 'static>>;
 
 /// Request
 #[async_trait]
@@ -24,8 +29,11 @@ pub trait Auth0RequestSimple {
 
 /// Request builder
 pub trait Auth0RequestBuilder {
-  /// Build request
-  fn build(&self, client: &Auth0Client) -> RequestBuilder;
+  /// Build description of the request, see [HttpRequest].
+  fn build(&self, client: &Auth0Client) -> HttpRequest;
+
+  /// Endpoint the request is sent to, see [Auth0Client::send_endpoint].
+  fn endpoint(&self) -> &'static Endpoint;
 }
 
 #[async_trait]
@@ -34,7 +42,9 @@ impl<A: Auth0RequestBuilder + Send + Sync> Auth0RequestSimple for A {
   where
     T: DeserializeOwned + Send + Sync,
   {
-    client.send(self.build(&client)).await
+    client
+      .send_endpoint(self.build(client), self.endpoint())
+      .await
   }
 }
 
@@ -45,8 +55,8 @@ impl<A: Auth0RequestBuilder + AsRef<Auth0Client> + Sync + Send> Auth0Request for
     T: DeserializeOwned + Send + Sync,
   {
     let client = self.as_ref();
-    let req = self.build(&client);
+    let req = self.build(client);
 
-    client.send(req).await
+    client.send_endpoint(req, self.endpoint()).await
   }
 }
@@ -2,21 +2,28 @@
 use serde::{Serialize, Serializer};
 
 /// Provides field sort order.
-#[derive(Copy, Clone, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum Ordering {
   /// Least to greatest order.
-  Ascending = -1,
+  Ascending = 1,
   /// Greatest to least order.
-  Descending = 1,
+  Descending = -1,
+}
+
+/// Field an endpoint can sort its results by.
+pub trait SortField {
+  /// Name of the field as defined by the Auth0 api.
+  fn name(&self) -> &'static str;
 }
 
 /// Provides field sort.
-pub struct Sort {
-  field: Option<String>,
+#[derive(Clone)]
+pub struct Sort<F> {
+  field: Option<F>,
   order: Option<Ordering>,
 }
 
-impl Sort {
+impl<F> Sort<F> {
   /// Create field sort.
   pub fn new() -> Self {
     Default::default()
@@ -28,34 +35,34 @@ impl Sort {
   }
 }
 
+impl<F> Default for Sort<F> {
+  fn default() -> Self {
+    Self {
+      field: None,
+      order: None,
+    }
+  }
+}
+
 /// Provides sort.
-pub trait Sortable {
+pub trait Sortable<F> {
   /// Sorting strategy.
   ///
   /// # Arguments
-  /// * `field` - The field name to sort by. (This will be the name defined by the Auth0 api)
+  /// * `field` - The field to sort by.
   /// * `order` - The order to sort field values.
-  fn sort(&mut self, field: &str, order: Ordering) -> &mut Self;
+  fn sort(&mut self, field: F, order: Ordering) -> &mut Self;
 }
 
-impl<S: AsMut<Sort>> Sortable for S {
-  fn sort(&mut self, field: &str, order: Ordering) -> &mut Self {
-    self.as_mut().field = Some(field.to_owned());
+impl<F: SortField, S: AsMut<Sort<F>>> Sortable<F> for S {
+  fn sort(&mut self, field: F, order: Ordering) -> &mut Self {
+    self.as_mut().field = Some(field);
     self.as_mut().order = Some(order);
     self
   }
 }
 
-impl Default for Sort {
-  fn default() -> Self {
-    Self {
-      field: None,
-      order: None,
-    }
-  }
-}
-
-impl Serialize for Sort {
+impl<F: SortField> Serialize for Sort<F> {
   fn serialize<S>(
     &self,
     serializer: S,
@@ -65,7 +72,7 @@ impl Serialize for Sort {
   {
     match (&self.field, self.order) {
       (Some(field), Some(order)) => {
-        serializer.serialize_str(&format!("{}:{}", field, order as i8))
+        serializer.serialize_str(&format!("{}:{}", field.name(), order as i8))
       }
       _ => serializer.serialize_none(),
     }
@@ -0,0 +1,174 @@
+//! Create an email verification ticket.
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
+use serde::Serialize;
+
+use crate::tickets::Ticket;
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Auth0Result, ClientId, Endpoint, HttpRequest, OrgId,
+  RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new(
+  "api/v2/tickets/email-verification",
+  &["create:user_tickets"],
+);
+
+/// Identity of the user for which a verification ticket is created.
+#[derive(Serialize, Clone, Debug)]
+pub struct TicketIdentity {
+  /// user_id of the identity to be verified.
+  pub user_id: String,
+  /// Identity provider name of the identity (e.g. `google-oauth2`).
+  pub provider: String,
+}
+
+/// Create an email verification ticket for a given user.
+#[derive(Serialize, Clone, Debug)]
+pub struct EmailVerificationTicketCreate {
+  #[serde(skip_serializing)]
+  client: Arc<Auth0Client>,
+
+  user_id: UserId,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  result_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  client_id: Option<ClientId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  organization_id: Option<OrgId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  ttl_sec: Option<u32>,
+  #[serde(rename = "includeEmailInRedirect")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  include_email_in_redirect: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  identity: Option<TicketIdentity>,
+
+  #[serde(skip)]
+  options: RequestOptions,
+}
+
+impl EmailVerificationTicketCreate {
+  /// Create email verification ticket request.
+  ///
+  /// # Arguments
+  /// * `user_id` - The user_id of the user for whom the ticket should be created.
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, user_id: I) -> Self {
+    Self {
+      client,
+
+      user_id: user_id.into(),
+      result_url: None,
+      client_id: None,
+      organization_id: None,
+      ttl_sec: None,
+      include_email_in_redirect: None,
+      identity: None,
+      options: Default::default(),
+    }
+  }
+
+  /// URL the user will be redirected to in the classic Universal Login experience once the
+  /// ticket is used.
+  pub fn result_url(&mut self, result_url: &str) -> &mut Self {
+    self.result_url = Some(result_url.to_owned());
+    self
+  }
+
+  /// ID of the client.  If provided for tenants using New Universal Login experience, the
+  /// user will be prompted to redirect to the default login route of the corresponding
+  /// application once the ticket is used.
+  pub fn client_id<I: Into<ClientId>>(&mut self, client_id: I) -> &mut Self {
+    self.client_id = Some(client_id.into());
+    self
+  }
+
+  /// ID of the organization.  If provided, organization parameters will be made available to
+  /// the email template and organization branding will be applied to the prompt.  In
+  /// addition, the redirect link in the prompt will include `organization_id` and
+  /// `organization_name` query string parameters.
+  pub fn organization_id<I: Into<OrgId>>(&mut self, organization_id: I) -> &mut Self {
+    self.organization_id = Some(organization_id.into());
+    self
+  }
+
+  /// Number of seconds for which the ticket is valid before expiration.  If unspecified or
+  /// set to 0, this value defaults to 432000 seconds (5 days).
+  pub fn ttl_sec(&mut self, ttl_sec: u32) -> &mut Self {
+    self.ttl_sec = Some(ttl_sec);
+    self
+  }
+
+  /// Whether to include the email address as part of the returnUrl in the reset_email
+  /// (true), or not (false).
+  pub fn include_email_in_redirect(
+    &mut self,
+    include_email_in_redirect: bool,
+  ) -> &mut Self {
+    self.include_email_in_redirect = Some(include_email_in_redirect);
+    self
+  }
+
+  /// Secondary identity to verify.  Required when verifying a linked (non-primary) identity.
+  ///
+  /// # Arguments
+  /// * `user_id` - user_id of the identity to be verified.
+  /// * `provider` - Identity provider name of the identity (e.g. `google-oauth2`).
+  pub fn identity(&mut self, user_id: &str, provider: &str) -> &mut Self {
+    self.identity = Some(TicketIdentity {
+      user_id: user_id.to_owned(),
+      provider: provider.to_owned(),
+    });
+    self
+  }
+
+  /// Send
+  pub async fn send(&self) -> Auth0Result<String> {
+    let ticket: Ticket = self
+      .client
+      .send_endpoint(self.build(&self.client), &ENDPOINT)
+      .await?;
+
+    Ok(ticket.ticket)
+  }
+}
+
+impl Auth0RequestBuilder for EmailVerificationTicketCreate {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::POST, ENDPOINT.path))
+      .json(self)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for EmailVerificationTicketCreate {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for EmailVerificationTicketCreate {
+  type Output = Auth0Result<String>;
+  type IntoFuture = SendFuture<String>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send().await })
+  }
+}
+
+impl IntoFuture for &mut EmailVerificationTicketCreate {
+  type Output = Auth0Result<String>;
+  type IntoFuture = SendFuture<String>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
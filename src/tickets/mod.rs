@@ -0,0 +1,58 @@
+#![warn(missing_docs)]
+//! Ticket request builders.
+
+#[doc(inline)]
+pub use email_verification_ticket_create::*;
+#[doc(inline)]
+pub use password_change_ticket_create::*;
+
+use crate::{Auth0Client, UserId};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub mod email_verification_ticket_create;
+pub mod password_change_ticket_create;
+
+/// Ticket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ticket {
+  /// URL representing the ticket.
+  pub ticket: String,
+}
+
+/// Tickets manager
+pub struct TicketsManager(Arc<Auth0Client>);
+
+impl TicketsManager {
+  /// Create tickets manager
+  pub fn new(client: Arc<Auth0Client>) -> Self {
+    Self(client)
+  }
+
+  /// Create an email verification ticket for a given user.  An email verification ticket is
+  /// a generated URL that the user can consume to verify their email address.
+  ///
+  /// # Arguments
+  /// * `user_id` - The user_id of the user for whom the ticket should be created.
+  /// # Scopes
+  /// * `create:user_tickets`
+  pub fn email_verification<I: Into<UserId>>(
+    &self,
+    user_id: I,
+  ) -> EmailVerificationTicketCreate {
+    EmailVerificationTicketCreate::new(self.0.clone(), user_id)
+  }
+
+  /// Create a password change ticket for a given user.  A password change ticket is a
+  /// generated URL that the user can consume to start a reset password flow.
+  ///
+  /// Note: This endpoint does not verify the given user's identity.  If you call this
+  /// endpoint within your application, you must design your application to verify the
+  /// user's identity.
+  ///
+  /// # Scopes
+  /// * `create:user_tickets`
+  pub fn password_change(&self) -> PasswordChangeTicketCreate {
+    PasswordChangeTicketCreate::new(self.0.clone())
+  }
+}
@@ -0,0 +1,180 @@
+//! Create a password change ticket.
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
+use serde::Serialize;
+
+use crate::tickets::Ticket;
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Auth0Result, ClientId, ConnectionId, Endpoint,
+  HttpRequest, OrgId, RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/tickets/password-change", &["create:user_tickets"]);
+
+/// Create a password change ticket for a given user.
+#[derive(Serialize, Clone, Debug)]
+pub struct PasswordChangeTicketCreate {
+  #[serde(skip_serializing)]
+  client: Arc<Auth0Client>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  result_url: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_id: Option<UserId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  client_id: Option<ClientId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  organization_id: Option<OrgId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  connection_id: Option<ConnectionId>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  email: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  ttl_sec: Option<u32>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  mark_email_as_verified: Option<bool>,
+  #[serde(rename = "includeEmailInRedirect")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  include_email_in_redirect: Option<bool>,
+
+  #[serde(skip)]
+  options: RequestOptions,
+}
+
+impl PasswordChangeTicketCreate {
+  /// Create password change ticket request.
+  pub fn new(client: Arc<Auth0Client>) -> Self {
+    Self {
+      client,
+
+      result_url: None,
+      user_id: None,
+      client_id: None,
+      organization_id: None,
+      connection_id: None,
+      email: None,
+      ttl_sec: None,
+      mark_email_as_verified: None,
+      include_email_in_redirect: None,
+      options: Default::default(),
+    }
+  }
+
+  /// URL the user will be redirected to once the ticket is used.
+  pub fn result_url(&mut self, result_url: &str) -> &mut Self {
+    self.result_url = Some(result_url.to_owned());
+    self
+  }
+
+  /// The user_id of for which the ticket is to be created.
+  pub fn user_id<I: Into<UserId>>(&mut self, user_id: I) -> &mut Self {
+    self.user_id = Some(user_id.into());
+    self
+  }
+
+  /// ID of the client.  If provided for tenants using New Universal Login experience, the
+  /// user will be prompted to redirect to the default login route of the corresponding
+  /// application once the ticket is used.
+  pub fn client_id<I: Into<ClientId>>(&mut self, client_id: I) -> &mut Self {
+    self.client_id = Some(client_id.into());
+    self
+  }
+
+  /// ID of the organization.  If provided, organization parameters will be made available to
+  /// the email template and organization branding will be applied to the prompt.  In
+  /// addition, the redirect link in the prompt will include `organization_id` and
+  /// `organization_name` query string parameters.
+  pub fn organization_id<I: Into<OrgId>>(&mut self, organization_id: I) -> &mut Self {
+    self.organization_id = Some(organization_id.into());
+    self
+  }
+
+  /// ID of the connection.  If provided, allows the user to be specified using email instead
+  /// of user_id.
+  pub fn connection_id<I: Into<ConnectionId>>(&mut self, connection_id: I) -> &mut Self {
+    self.connection_id = Some(connection_id.into());
+    self
+  }
+
+  /// Email address of the user for whom the tickets should be created.  Requires the
+  /// `connection_id` parameter.  Cannot be specified when using `user_id`.
+  pub fn email(&mut self, email: &str) -> &mut Self {
+    self.email = Some(email.to_owned());
+    self
+  }
+
+  /// Number of seconds for which the ticket is valid before expiration.  If unspecified or
+  /// set to 0, this value defaults to 432000 seconds (5 days).
+  pub fn ttl_sec(&mut self, ttl_sec: u32) -> &mut Self {
+    self.ttl_sec = Some(ttl_sec);
+    self
+  }
+
+  /// Whether to set the email_verified attribute to true (true) or whether it should not be
+  /// updated (false).
+  pub fn mark_email_as_verified(&mut self, mark_email_as_verified: bool) -> &mut Self {
+    self.mark_email_as_verified = Some(mark_email_as_verified);
+    self
+  }
+
+  /// Whether to include the email address as part of the returnUrl in the reset_email
+  /// (true), or not (false).
+  pub fn include_email_in_redirect(
+    &mut self,
+    include_email_in_redirect: bool,
+  ) -> &mut Self {
+    self.include_email_in_redirect = Some(include_email_in_redirect);
+    self
+  }
+
+  /// Send
+  pub async fn send(&self) -> Auth0Result<String> {
+    let ticket: Ticket = self
+      .client
+      .send_endpoint(self.build(&self.client), &ENDPOINT)
+      .await?;
+
+    Ok(ticket.ticket)
+  }
+}
+
+impl Auth0RequestBuilder for PasswordChangeTicketCreate {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::POST, ENDPOINT.path))
+      .json(self)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for PasswordChangeTicketCreate {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for PasswordChangeTicketCreate {
+  type Output = Auth0Result<String>;
+  type IntoFuture = SendFuture<String>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send().await })
+  }
+}
+
+impl IntoFuture for &mut PasswordChangeTicketCreate {
+  type Output = Auth0Result<String>;
+  type IntoFuture = SendFuture<String>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -0,0 +1,118 @@
+//! Private key JWT client authentication.
+use std::fmt::{Debug, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::token::TokenError;
+
+/// The `client_assertion_type` sent alongside a signed client assertion.
+pub const CLIENT_ASSERTION_TYPE: &str =
+  "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+/// Lifetime of a client assertion in seconds.
+const CLIENT_ASSERTION_TTL: u64 = 60;
+
+/// Algorithm used to sign client assertions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+pub enum SigningAlgorithm {
+  /// RSASSA-PKCS1-v1_5 using SHA-256.
+  RS256,
+  /// RSASSA-PSS using SHA-256.
+  PS256,
+  /// ECDSA using P-256 and SHA-256.
+  ES256,
+}
+
+impl From<SigningAlgorithm> for Algorithm {
+  fn from(algorithm: SigningAlgorithm) -> Self {
+    match algorithm {
+      SigningAlgorithm::RS256 => Algorithm::RS256,
+      SigningAlgorithm::PS256 => Algorithm::PS256,
+      SigningAlgorithm::ES256 => Algorithm::ES256,
+    }
+  }
+}
+
+/// Private key used to sign a client assertion for every token request.
+#[derive(Clone)]
+pub struct PrivateKeyJwt {
+  key: EncodingKey,
+  key_id: String,
+  algorithm: SigningAlgorithm,
+}
+
+#[derive(Serialize)]
+struct ClientAssertionClaims<'a> {
+  iss: &'a str,
+  sub: &'a str,
+  aud: &'a str,
+  iat: u64,
+  exp: u64,
+  jti: String,
+}
+
+impl PrivateKeyJwt {
+  /// Create private key from a PEM encoded key.
+  ///
+  /// # Arguments
+  /// * `pem` - PEM encoded RSA (`RS256`, `PS256`) or EC (`ES256`) private key.
+  /// * `key_id` - The id of the key registered with the application, sent as `kid`.
+  /// * `algorithm` - The algorithm used to sign client assertions.
+  pub fn from_pem(
+    pem: &[u8],
+    key_id: &str,
+    algorithm: SigningAlgorithm,
+  ) -> Result<Self, TokenError> {
+    let key = match algorithm {
+      SigningAlgorithm::RS256 | SigningAlgorithm::PS256 => {
+        EncodingKey::from_rsa_pem(pem)?
+      }
+      SigningAlgorithm::ES256 => EncodingKey::from_ec_pem(pem)?,
+    };
+    let key = Self {
+      key,
+      key_id: key_id.to_owned(),
+      algorithm,
+    };
+
+    // Keys are only parsed once used, sign a throwaway assertion to fail early.
+    key.sign("validate", "validate")?;
+
+    Ok(key)
+  }
+
+  /// Sign a client assertion.
+  ///
+  /// # Arguments
+  /// * `client_id` - The client id used as issuer and subject.
+  /// * `audience` - The url of the tenant with a trailing slash, e.g.
+  ///   `https://example.auth0.com/`.
+  pub fn sign(&self, client_id: &str, audience: &str) -> Result<String, TokenError> {
+    let iat = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let mut header = Header::new(self.algorithm.into());
+    header.kid = Some(self.key_id.clone());
+
+    let claims = ClientAssertionClaims {
+      iss: client_id,
+      sub: client_id,
+      aud: audience,
+      iat,
+      exp: iat + CLIENT_ASSERTION_TTL,
+      jti: Uuid::new_v4().to_string(),
+    };
+
+    Ok(encode(&header, &claims, &self.key)?)
+  }
+}
+
+impl Debug for PrivateKeyJwt {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("PrivateKeyJwt")
+      .field("key_id", &self.key_id)
+      .field("algorithm", &self.algorithm)
+      .finish()
+  }
+}
@@ -0,0 +1,345 @@
+//! Access token caches shared across token managers and process restarts.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_mutex::Mutex as AsyncMutex;
+use async_trait::async_trait;
+use blocking::unblock;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use crate::token::TokenError;
+
+/// Identifies the tenant, API and application a token was issued for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenCacheKey {
+  /// The auth0 tenant domain.
+  pub domain: String,
+  /// The audience the token was issued for.
+  pub audience: String,
+  /// The client id of the application the token was issued to.
+  pub client_id: String,
+}
+
+impl TokenCacheKey {
+  /// Get string uniquely identifying this key.
+  pub fn id(&self) -> String {
+    format!("{} {} {}", self.domain, self.audience, self.client_id)
+  }
+}
+
+/// Access token and its validity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedToken {
+  /// Encoded JWT access token.
+  pub access_token: String,
+  /// Time the token was issued at.
+  #[serde(with = "unix_secs")]
+  pub issued_at: SystemTime,
+  /// Time the token expires at.
+  #[serde(with = "unix_secs")]
+  pub expires_at: SystemTime,
+}
+
+impl CachedToken {
+  /// Get the time the token should be refreshed at.
+  ///
+  /// # Arguments
+  /// * `leeway` - Time before expiration to refresh at, capped at half of the lifetime.
+  pub fn refresh_at(&self, leeway: Duration) -> SystemTime {
+    let lifetime = self
+      .expires_at
+      .duration_since(self.issued_at)
+      .unwrap_or_default();
+
+    self.expires_at - leeway.min(lifetime / 2)
+  }
+}
+
+/// Storage of access tokens.
+///
+/// Caches are consulted by [TokenManager](crate::token::TokenManager) before requesting a
+/// new token and updated after every token request.  Errors are not fatal, the manager
+/// falls back to requesting a new token.
+#[async_trait]
+pub trait TokenCache: Send + Sync {
+  /// Get cached token for key.
+  async fn get(&self, key: &TokenCacheKey) -> Result<Option<CachedToken>, TokenError>;
+  /// Store token for key.
+  async fn set(&self, key: &TokenCacheKey, token: &CachedToken)
+    -> Result<(), TokenError>;
+  /// Remove cached token for key.
+  async fn remove(&self, key: &TokenCacheKey) -> Result<(), TokenError>;
+}
+
+#[async_trait]
+impl<C: TokenCache + ?Sized> TokenCache for Arc<C> {
+  async fn get(&self, key: &TokenCacheKey) -> Result<Option<CachedToken>, TokenError> {
+    (**self).get(key).await
+  }
+
+  async fn set(
+    &self,
+    key: &TokenCacheKey,
+    token: &CachedToken,
+  ) -> Result<(), TokenError> {
+    (**self).set(key, token).await
+  }
+
+  async fn remove(&self, key: &TokenCacheKey) -> Result<(), TokenError> {
+    (**self).remove(key).await
+  }
+}
+
+/// Caches tokens in memory, shared by every token manager using the same instance.
+#[derive(Debug, Default)]
+pub struct MemoryTokenCache {
+  tokens: Mutex<HashMap<TokenCacheKey, CachedToken>>,
+}
+
+impl MemoryTokenCache {
+  /// Create empty memory cache.
+  pub fn new() -> Self {
+    Default::default()
+  }
+}
+
+#[async_trait]
+impl TokenCache for MemoryTokenCache {
+  async fn get(&self, key: &TokenCacheKey) -> Result<Option<CachedToken>, TokenError> {
+    Ok(self.tokens.lock().unwrap().get(key).cloned())
+  }
+
+  async fn set(
+    &self,
+    key: &TokenCacheKey,
+    token: &CachedToken,
+  ) -> Result<(), TokenError> {
+    self
+      .tokens
+      .lock()
+      .unwrap()
+      .insert(key.clone(), token.clone());
+    Ok(())
+  }
+
+  async fn remove(&self, key: &TokenCacheKey) -> Result<(), TokenError> {
+    self.tokens.lock().unwrap().remove(key);
+    Ok(())
+  }
+}
+
+/// Caches tokens in a JSON file so they survive process restarts.
+///
+/// On unix the file is only readable and writable by the owner (`0600`) and is replaced
+/// atomically on every write, processes sharing the file never see a partial write.
+/// Expired tokens are dropped whenever the file is written.  A corrupt file is reported as
+/// [TokenError::Cache] when reading and replaced by the next write.
+///
+/// File operations run on a background thread pool, they never block the executor.
+#[derive(Debug)]
+pub struct FileTokenCache {
+  path: PathBuf,
+  lock: AsyncMutex<()>,
+}
+
+impl FileTokenCache {
+  /// Create file cache.
+  ///
+  /// # Arguments
+  /// * `path` - The path of the cache file, missing parent directories are created.
+  pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+    Self {
+      path: path.into(),
+      lock: AsyncMutex::new(()),
+    }
+  }
+
+  /// Get the default cache file path, `$XDG_CACHE_HOME/auth0-management/tokens.json` or
+  /// `$HOME/.cache/auth0-management/tokens.json`.
+  pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+      .map(PathBuf::from)
+      .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+      .map(|dir| dir.join("auth0-management").join("tokens.json"))
+  }
+
+  /// Get the path of the cache file.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Apply `update` to the tokens of the cache file on a background thread.
+  async fn update<F>(&self, update: F) -> Result<(), TokenError>
+  where
+    F: FnOnce(&mut HashMap<String, CachedToken>) + Send + 'static,
+  {
+    let _lock = self.lock.lock().await;
+    let path = self.path.clone();
+
+    unblock(move || update_file(&path, update)).await
+  }
+}
+
+#[async_trait]
+impl TokenCache for FileTokenCache {
+  async fn get(&self, key: &TokenCacheKey) -> Result<Option<CachedToken>, TokenError> {
+    let _lock = self.lock.lock().await;
+    let path = self.path.clone();
+    let id = key.id();
+
+    unblock(move || Ok(read_tokens(&path)?.remove(&id))).await
+  }
+
+  async fn set(
+    &self,
+    key: &TokenCacheKey,
+    token: &CachedToken,
+  ) -> Result<(), TokenError> {
+    let id = key.id();
+    let token = token.clone();
+
+    self
+      .update(move |tokens| {
+        tokens.insert(id, token);
+      })
+      .await
+  }
+
+  async fn remove(&self, key: &TokenCacheKey) -> Result<(), TokenError> {
+    let id = key.id();
+
+    self
+      .update(move |tokens| {
+        tokens.remove(&id);
+      })
+      .await
+  }
+}
+
+fn read_tokens(path: &Path) -> Result<HashMap<String, CachedToken>, TokenError> {
+  match read_file(path)? {
+    Some(data) => serde_json::from_slice(&data).map_err(|err| {
+      TokenError::Cache(format!("corrupt token cache {}: {}", path.display(), err).into())
+    }),
+    None => Ok(HashMap::new()),
+  }
+}
+
+fn read_file(path: &Path) -> Result<Option<Vec<u8>>, TokenError> {
+  match fs::read(path) {
+    Ok(data) => Ok(Some(data)),
+    Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+    Err(err) => Err(TokenError::Cache(err.into())),
+  }
+}
+
+fn write_tokens(
+  path: &Path,
+  tokens: &HashMap<String, CachedToken>,
+) -> Result<(), TokenError> {
+  let write = || -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+      create_private_dir(dir)?;
+    }
+
+    // Every writer uses its own temporary file, processes sharing the cache would
+    // otherwise overwrite each other's file before renaming it.
+    let tmp = path.with_file_name(format!(
+      ".{}.{}.{}.tmp",
+      path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default(),
+      std::process::id(),
+      Uuid::new_v4().simple()
+    ));
+    let res = private_options().open(&tmp).and_then(|mut file| {
+      file.write_all(&serde_json::to_vec(tokens)?)?;
+      file.sync_all()?;
+
+      fs::rename(&tmp, path)
+    });
+    if res.is_err() {
+      let _ = fs::remove_file(&tmp);
+    }
+
+    res
+  };
+
+  write().map_err(|err| TokenError::Cache(err.into()))
+}
+
+fn update_file<F>(path: &Path, update: F) -> Result<(), TokenError>
+where
+  F: FnOnce(&mut HashMap<String, CachedToken>),
+{
+  let now = SystemTime::now();
+  // A corrupt file is replaced, reads report it.
+  let mut tokens: HashMap<String, CachedToken> = match read_file(path)? {
+    Some(data) => serde_json::from_slice(&data).unwrap_or_default(),
+    None => HashMap::new(),
+  };
+
+  tokens.retain(|_, token| token.expires_at > now);
+  update(&mut tokens);
+
+  write_tokens(path, &tokens)
+}
+
+#[cfg(unix)]
+fn private_options() -> OpenOptions {
+  use std::os::unix::fs::OpenOptionsExt;
+
+  let mut options = OpenOptions::new();
+  options.write(true).create(true).truncate(true).mode(0o600);
+  options
+}
+
+#[cfg(not(unix))]
+fn private_options() -> OpenOptions {
+  let mut options = OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+  options
+}
+
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+  use std::os::unix::fs::DirBuilderExt;
+
+  fs::DirBuilder::new()
+    .recursive(true)
+    .mode(0o700)
+    .create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+  fs::create_dir_all(dir)
+}
+
+mod unix_secs {
+  use super::*;
+
+  pub fn serialize<S: Serializer>(
+    time: &SystemTime,
+    serializer: S,
+  ) -> Result<S::Ok, S::Error> {
+    let secs = time
+      .duration_since(UNIX_EPOCH)
+      .map_err(serde::ser::Error::custom)?
+      .as_secs();
+
+    serializer.serialize_u64(secs)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+  ) -> Result<SystemTime, D::Error> {
+    Ok(UNIX_EPOCH + Duration::from_secs(u64::deserialize(deserializer)?))
+  }
+}
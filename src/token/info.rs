@@ -0,0 +1,83 @@
+//! Inspect the scopes, expiry and claims of access tokens.
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::{Map, Value};
+
+use crate::token::TokenError;
+
+/// Decoded payload of a management API access token.
+///
+/// The signature is not verified, the token is only inspected to learn what the issuing
+/// tenant granted.  Auth0 verifies the token on every request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenInfo {
+  /// Scopes granted to the application.
+  pub scopes: Vec<String>,
+  /// Time the token was issued at.
+  pub issued_at: Option<SystemTime>,
+  /// Time the token expires at.
+  pub expires_at: Option<SystemTime>,
+  /// Every claim of the token.
+  pub claims: Map<String, Value>,
+}
+
+impl TokenInfo {
+  /// Decode encoded JWT access token.
+  ///
+  /// # Arguments
+  /// * `token` - The encoded JWT access token.
+  pub fn decode(token: &str) -> Result<Self, TokenError> {
+    let payload = token
+      .split('.')
+      .nth(1)
+      .ok_or_else(|| TokenError::Malformed("token is not a JWT".to_owned()))?;
+    let payload = URL_SAFE_NO_PAD
+      .decode(payload.trim_end_matches('='))
+      .map_err(|err| TokenError::Malformed(err.to_string()))?;
+    let claims = match serde_json::from_slice(&payload) {
+      Ok(Value::Object(claims)) => claims,
+      Ok(_) => return Err(TokenError::Malformed("claims are not an object".to_owned())),
+      Err(err) => return Err(TokenError::Malformed(err.to_string())),
+    };
+
+    // Auth0 sends scopes space delimited, some issuers use an array.
+    let scopes = match claims.get("scope").or_else(|| claims.get("scp")) {
+      Some(Value::String(scope)) => scope.split_whitespace().map(str::to_owned).collect(),
+      Some(Value::Array(scopes)) => scopes
+        .iter()
+        .filter_map(|scope| scope.as_str().map(str::to_owned))
+        .collect(),
+      _ => vec![],
+    };
+
+    Ok(Self {
+      scopes,
+      issued_at: timestamp(&claims, "iat"),
+      expires_at: timestamp(&claims, "exp"),
+      claims,
+    })
+  }
+
+  /// Determines if `scope` was granted.
+  pub fn has_scope(&self, scope: &str) -> bool {
+    self.scopes.iter().any(|granted| granted == scope)
+  }
+
+  /// Determines if any of `scopes` was granted, an empty list is always satisfied.
+  pub fn has_any_scope(&self, scopes: &[&str]) -> bool {
+    scopes.is_empty() || scopes.iter().any(|scope| self.has_scope(scope))
+  }
+
+  /// Determines if the token expired.
+  pub fn is_expired(&self) -> bool {
+    matches!(self.expires_at, Some(expires_at) if expires_at <= SystemTime::now())
+  }
+}
+
+fn timestamp(claims: &Map<String, Value>, name: &str) -> Option<SystemTime> {
+  let secs = claims.get(name)?.as_u64()?;
+
+  Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
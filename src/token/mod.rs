@@ -0,0 +1,405 @@
+use std::error::Error;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, SystemTimeError};
+
+use async_mutex::Mutex;
+use futures_timer::Delay;
+use http::{Method, StatusCode};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+pub use assertion::*;
+pub use cache::*;
+pub use info::*;
+pub use provider::*;
+
+use crate::client::tenant_url;
+use crate::middleware::{execute, Middleware};
+use crate::trace;
+use crate::{HttpError, HttpRequest, HttpTransport, ReqwestTransport};
+
+pub mod assertion;
+pub mod cache;
+pub mod info;
+pub mod provider;
+
+/// Auth0 OAuth token.
+#[derive(Deserialize)]
+pub struct Token {
+  /// Space delimited scopes granted to the application.
+  #[serde(default)]
+  pub scope: Option<String>,
+  /// Type of the token, always `Bearer`.
+  #[serde(default)]
+  pub token_type: Option<String>,
+  /// Token expiration in seconds.
+  pub expires_in: u64,
+  /// Encoded JWT access token.
+  pub access_token: String,
+}
+
+#[derive(Debug)]
+pub enum TokenError {
+  Time(SystemTimeError),
+  Transport(HttpError),
+  AccessDenied(String),
+  Key(jsonwebtoken::errors::Error),
+  Provider(Box<dyn Error + Send + Sync>),
+  Cache(Box<dyn Error + Send + Sync>),
+  Malformed(String),
+}
+
+/// Credentials used to authenticate the application with the token endpoint.
+#[derive(Clone)]
+pub enum ClientCredentials {
+  /// Client secret sent with every token request.
+  Secret(String),
+  /// Private key used to sign a client assertion for every token request.
+  PrivateKey(PrivateKeyJwt),
+}
+
+#[derive(Clone, Debug)]
+struct TokenOpts {
+  audience: String,
+  grant_type: String,
+  client_id: String,
+  credentials: ClientCredentials,
+}
+
+#[derive(Serialize)]
+struct TokenRequest<'a> {
+  audience: &'a str,
+  grant_type: &'a str,
+  client_id: &'a str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  client_secret: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  client_assertion_type: Option<&'a str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  client_assertion: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct TokenErrorResponse {
+  #[allow(dead_code)]
+  error: String,
+  error_description: String,
+}
+
+/// Default time before expiration at which tokens are refreshed.
+pub const DEFAULT_REFRESH_LEEWAY: Duration = Duration::from_secs(60);
+
+/// Delay before retrying a failed background refresh.
+const REFRESH_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Provides oauth token retrieval and expiration checks.
+///
+/// Tokens are refreshed ahead of their expiration, see [TokenManager::refresh_leeway].
+/// Concurrent callers share a single token request.
+pub struct TokenManager {
+  transport: Arc<dyn HttpTransport>,
+  domain: String,
+
+  token: Mutex<Option<CachedToken>>,
+  token_opts: TokenOpts,
+  leeway: Duration,
+  cache: Option<Arc<dyn TokenCache>>,
+  middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl TokenManager {
+  /// Gets builder for [TokenManager].
+  pub fn new(
+    client: Client,
+    domain: &str,
+    audience: &str,
+    client_id: &str,
+    credentials: ClientCredentials,
+  ) -> Self {
+    Self::with_transport(
+      Arc::new(ReqwestTransport::new(client)),
+      domain,
+      audience,
+      client_id,
+      credentials,
+    )
+  }
+
+  /// Gets builder for [TokenManager] requesting tokens with `transport`.
+  pub fn with_transport(
+    transport: Arc<dyn HttpTransport>,
+    domain: &str,
+    audience: &str,
+    client_id: &str,
+    credentials: ClientCredentials,
+  ) -> Self {
+    Self {
+      transport,
+      domain: domain.to_owned(),
+      token: Mutex::new(None),
+      token_opts: TokenOpts {
+        audience: audience.to_owned(),
+        grant_type: "client_credentials".to_owned(),
+        client_id: client_id.to_owned(),
+        credentials,
+      },
+      leeway: DEFAULT_REFRESH_LEEWAY,
+      cache: None,
+      middleware: Vec::new(),
+    }
+  }
+
+  /// Call `middleware` around every token request, see [Middleware].
+  pub fn middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+    self.middleware.push(middleware);
+    self
+  }
+
+  /// Cache tokens in `cache`, tokens found in the cache are used while they are valid instead
+  /// of requesting new ones.
+  pub fn cache(mut self, cache: Arc<dyn TokenCache>) -> Self {
+    self.cache = Some(cache);
+    self
+  }
+
+  /// Get key identifying tokens of this manager in a [TokenCache].
+  pub fn cache_key(&self) -> TokenCacheKey {
+    TokenCacheKey {
+      domain: self.domain.clone(),
+      audience: self.token_opts.audience.clone(),
+      client_id: self.token_opts.client_id.clone(),
+    }
+  }
+
+  /// Time before expiration at which tokens are refreshed.  Defaults to
+  /// [DEFAULT_REFRESH_LEEWAY].
+  ///
+  /// The leeway is capped at half of the token lifetime.
+  pub fn refresh_leeway(mut self, leeway: Duration) -> Self {
+    self.leeway = leeway;
+    self
+  }
+
+  /// Gets valid encoded JWT token.
+  ///
+  /// Only one token request is made at a time, concurrent callers wait for it and share the
+  /// fetched token.
+  pub async fn get_token(&self) -> Result<String, TokenError> {
+    Ok(self.get_cached_token().await?.access_token)
+  }
+
+  /// Gets scopes, expiry and claims of the current token, a token is fetched if needed.
+  pub async fn token_info(&self) -> Result<TokenInfo, TokenError> {
+    TokenInfo::decode(&self.get_token().await?)
+  }
+
+  /// Gets token which isn't due for a refresh.
+  async fn get_cached_token(&self) -> Result<CachedToken, TokenError> {
+    let mut token = self.token.lock().await;
+
+    if token.is_none() {
+      *token = self.load_token().await;
+    }
+
+    if let Some(token) = &*token {
+      if SystemTime::now() < token.refresh_at(self.leeway) {
+        return Ok(token.clone());
+      }
+    }
+
+    let fetched = self.fetch_token().await?;
+    self.store_token(&fetched).await;
+    *token = Some(fetched.clone());
+
+    Ok(fetched)
+  }
+
+  /// Stores token in the cache, cache errors are traced and ignored.
+  async fn store_token(&self, token: &CachedToken) {
+    if let Some(cache) = &self.cache {
+      if let Err(err) = cache.set(&self.cache_key(), token).await {
+        trace::token_cache_failed(&err);
+      }
+    }
+  }
+
+  /// Gets token from cache, cache errors are traced and treated as a miss.
+  async fn load_token(&self) -> Option<CachedToken> {
+    let cache = self.cache.as_ref()?;
+
+    cache
+      .get(&self.cache_key())
+      .await
+      .map_err(|err| trace::token_cache_failed(&err))
+      .ok()
+      .flatten()
+  }
+
+  /// Discards the cached token if it is `access_token`, the next call to
+  /// [TokenManager::get_token] fetches a new token.
+  pub async fn invalidate(&self, access_token: &str) {
+    let mut token = self.token.lock().await;
+
+    if matches!(&*token, Some(token) if token.access_token == access_token) {
+      *token = None;
+
+      if let Some(cache) = &self.cache {
+        if let Err(err) = cache.remove(&self.cache_key()).await {
+          trace::token_cache_failed(&err);
+        }
+      }
+    }
+  }
+
+  /// Keeps the token fresh by refreshing it ahead of expiration, never completes.
+  ///
+  /// Tokens are refreshed at twice the refresh leeway before expiration, before requests
+  /// would refresh them.  The new token is fetched without blocking requests, they keep
+  /// using the current token until it is replaced.  Failed refreshes are retried after a
+  /// short delay.
+  pub async fn refresh_ahead(&self) {
+    loop {
+      let delay = match self.get_cached_token().await {
+        Ok(token) => token
+          .refresh_at(self.leeway * 2)
+          .duration_since(SystemTime::now())
+          .unwrap_or_default(),
+        Err(_) => REFRESH_RETRY_DELAY,
+      };
+      Delay::new(delay.max(Duration::from_millis(100))).await;
+
+      if self.refresh_token().await.is_err() {
+        Delay::new(REFRESH_RETRY_DELAY).await;
+      }
+    }
+  }
+
+  /// Fetches a new token and replaces the current one, the token isn't locked while
+  /// fetching.
+  async fn refresh_token(&self) -> Result<(), TokenError> {
+    let fetched = self.fetch_token().await?;
+    self.store_token(&fetched).await;
+    *self.token.lock().await = Some(fetched);
+
+    Ok(())
+  }
+
+  /// Gets new encoded JWT token from auth0.
+  async fn fetch_token(&self) -> Result<CachedToken, TokenError> {
+    let res = trace::instrument_token(self.request_token()).await;
+
+    match &res {
+      Ok(token) => trace::token_fetched(
+        token
+          .expires_at
+          .duration_since(token.issued_at)
+          .unwrap_or_default(),
+      ),
+      Err(err) => trace::token_failed(err),
+    }
+
+    res
+  }
+
+  async fn request_token(&self) -> Result<CachedToken, TokenError> {
+    let url = tenant_url(&self.domain, "oauth/token");
+    let opts = &self.token_opts;
+    let mut req = TokenRequest {
+      audience: &opts.audience,
+      grant_type: &opts.grant_type,
+      client_id: &opts.client_id,
+      client_secret: None,
+      client_assertion_type: None,
+      client_assertion: None,
+    };
+
+    match &opts.credentials {
+      ClientCredentials::Secret(secret) => req.client_secret = Some(secret),
+      ClientCredentials::PrivateKey(key) => {
+        req.client_assertion_type = Some(CLIENT_ASSERTION_TYPE);
+        let audience = tenant_url(&self.domain, "");
+        req.client_assertion = Some(key.sign(&opts.client_id, &audience)?);
+      }
+    }
+
+    let res = execute(
+      &*self.transport,
+      &self.middleware,
+      HttpRequest::new(Method::POST, url).form(&req),
+    )
+    .await?;
+
+    if res.status != StatusCode::OK {
+      return Err(serde_json::from_slice::<TokenErrorResponse>(&res.body)?.into());
+    }
+
+    let token: Token = serde_json::from_slice(&res.body)?;
+    let now = SystemTime::now();
+
+    Ok(CachedToken {
+      access_token: token.access_token,
+      issued_at: now,
+      expires_at: now + Duration::from_secs(token.expires_in),
+    })
+  }
+}
+
+impl Debug for TokenManager {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("TokenManager")
+      .field("domain", &self.domain)
+      .field("token_opts", &self.token_opts)
+      .field("leeway", &self.leeway)
+      .finish()
+  }
+}
+
+impl std::fmt::Display for TokenError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+impl From<TokenErrorResponse> for TokenError {
+  fn from(res: TokenErrorResponse) -> TokenError {
+    TokenError::AccessDenied(res.error_description)
+  }
+}
+
+impl From<SystemTimeError> for TokenError {
+  fn from(err: SystemTimeError) -> TokenError {
+    TokenError::Time(err)
+  }
+}
+
+impl From<jsonwebtoken::errors::Error> for TokenError {
+  fn from(err: jsonwebtoken::errors::Error) -> TokenError {
+    TokenError::Key(err)
+  }
+}
+
+impl From<HttpError> for TokenError {
+  fn from(err: HttpError) -> TokenError {
+    TokenError::Transport(err)
+  }
+}
+
+impl From<serde_json::Error> for TokenError {
+  fn from(err: serde_json::Error) -> TokenError {
+    TokenError::Malformed(err.to_string())
+  }
+}
+
+impl Error for TokenError {}
+
+impl Debug for ClientCredentials {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ClientCredentials::Secret(_) => f.write_str("Secret(..)"),
+      ClientCredentials::PrivateKey(key) => {
+        f.debug_tuple("PrivateKey").field(key).finish()
+      }
+    }
+  }
+}
@@ -0,0 +1,97 @@
+//! Access token providers.
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+
+use async_trait::async_trait;
+
+use crate::token::{TokenError, TokenManager};
+
+/// Provides the access token sent with every management API request.
+///
+/// [TokenManager] is the default provider and fetches tokens using the client credentials
+/// grant.  Implement this trait to source tokens elsewhere, e.g. from a vault, a sidecar or a
+/// shared token cache service.  Async closures returning a token are providers as well.
+///
+/// ```
+/// use auth0_management::Auth0;
+/// use auth0_management::token::TokenError;
+///
+/// async fn token_from_sidecar() -> Result<String, TokenError> {
+///   Ok("ACCESS_TOKEN".to_owned())
+/// }
+///
+/// let auth0 = Auth0::builder()
+///   .domain("example.auth0.com")
+///   .token_provider(token_from_sidecar)
+///   .build()
+///   .unwrap();
+/// ```
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+  /// Gets valid encoded JWT token.
+  async fn get_token(&self) -> Result<String, TokenError>;
+
+  /// Called when `token` was rejected by Auth0, providers which cache tokens should discard
+  /// it.
+  async fn invalidate(&self, _token: &str) {}
+
+  /// Keeps the token fresh in the background.
+  ///
+  /// Providers which refresh ahead of expiration never complete, others return immediately.
+  async fn refresh(&self) {}
+}
+
+#[async_trait]
+impl TokenProvider for TokenManager {
+  async fn get_token(&self) -> Result<String, TokenError> {
+    TokenManager::get_token(self).await
+  }
+
+  async fn invalidate(&self, token: &str) {
+    TokenManager::invalidate(self, token).await
+  }
+
+  async fn refresh(&self) {
+    self.refresh_ahead().await
+  }
+}
+
+#[async_trait]
+impl<F, R> TokenProvider for F
+where
+  F: Fn() -> R + Send + Sync,
+  R: Future<Output = Result<String, TokenError>> + Send,
+{
+  async fn get_token(&self) -> Result<String, TokenError> {
+    self().await
+  }
+}
+
+/// Provides the same access token for every request.
+///
+/// Useful for scripts which are handed a token, the token is never refreshed.
+#[derive(Clone)]
+pub struct StaticToken(String);
+
+impl StaticToken {
+  /// Create static token provider.
+  ///
+  /// # Arguments
+  /// * `token` - The encoded JWT access token.
+  pub fn new(token: &str) -> Self {
+    Self(token.to_owned())
+  }
+}
+
+#[async_trait]
+impl TokenProvider for StaticToken {
+  async fn get_token(&self) -> Result<String, TokenError> {
+    Ok(self.0.clone())
+  }
+}
+
+impl Debug for StaticToken {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.write_str("StaticToken(..)")
+  }
+}
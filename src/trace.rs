@@ -0,0 +1,140 @@
+//! Tracing instrumentation, every function is a no-op without the `tracing` feature.
+//!
+//! Only methods, path templates, statuses and counters are recorded, headers, bodies, ids and
+//! tokens are never part of spans or events.
+use std::future::Future;
+use std::time::Duration;
+
+#[cfg(feature = "tracing")]
+use http::StatusCode;
+#[cfg(feature = "tracing")]
+use tracing::{field, Instrument, Span};
+
+use crate::token::TokenError;
+use crate::{HttpRequest, HttpResponse};
+
+/// Run `fut` in a span describing a management API request.
+///
+/// # Arguments
+/// * `path` - The path template of the endpoint, if known.
+#[cfg(feature = "tracing")]
+pub(crate) fn instrument_request<F: Future>(
+  path: Option<&'static str>,
+  fut: F,
+) -> impl Future<Output = F::Output> {
+  let span = tracing::info_span!(
+    "auth0.request",
+    method = field::Empty,
+    path = path,
+    status = field::Empty,
+    attempt = field::Empty,
+    rate_limit_remaining = field::Empty,
+  );
+
+  fut.instrument(span)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn instrument_request<F: Future>(
+  _path: Option<&'static str>,
+  fut: F,
+) -> impl Future<Output = F::Output> {
+  fut
+}
+
+/// Run `fut` in a span describing a token request.
+#[cfg(feature = "tracing")]
+pub(crate) fn instrument_token<F: Future>(fut: F) -> impl Future<Output = F::Output> {
+  let span = tracing::info_span!(
+    "auth0.token",
+    method = field::Empty,
+    path = "oauth/token",
+    status = field::Empty,
+  );
+
+  fut.instrument(span)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn instrument_token<F: Future>(fut: F) -> impl Future<Output = F::Output> {
+  fut
+}
+
+/// Record the number of the attempt being sent.
+pub(crate) fn record_attempt(_attempt: u32) {
+  #[cfg(feature = "tracing")]
+  Span::current().record("attempt", _attempt);
+}
+
+/// Record the method of a request about to be sent.
+pub(crate) fn record_request(_req: &HttpRequest) {
+  #[cfg(feature = "tracing")]
+  Span::current().record("method", _req.method.as_str());
+}
+
+/// Record the status and rate limit of a response.
+pub(crate) fn record_response(_res: &HttpResponse) {
+  #[cfg(feature = "tracing")]
+  {
+    let span = Span::current();
+    let header = |name: &str| {
+      _res
+        .headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+    };
+
+    span.record("status", _res.status.as_u16());
+    if let Some(remaining) = header("x-ratelimit-remaining") {
+      span.record("rate_limit_remaining", remaining);
+    }
+    if _res.status == StatusCode::TOO_MANY_REQUESTS {
+      tracing::warn!(
+        rate_limit_reset = header("x-ratelimit-reset"),
+        "auth0 rate limit exceeded"
+      );
+    }
+  }
+}
+
+/// Record that a request waits for the rate limit to reset.
+pub(crate) fn throttled(_wait: Duration) {
+  #[cfg(feature = "tracing")]
+  tracing::info!(
+    wait_ms = _wait.as_millis() as u64,
+    "auth0 rate limit reached, waiting"
+  );
+}
+
+/// Record that the access token was rejected and the request is retried.
+pub(crate) fn token_rejected() {
+  #[cfg(feature = "tracing")]
+  tracing::info!("access token rejected, retrying with a new token");
+}
+
+/// Record a fetched access token.
+pub(crate) fn token_fetched(_expires_in: Duration) {
+  #[cfg(feature = "tracing")]
+  tracing::info!(expires_in = _expires_in.as_secs(), "access token refreshed");
+}
+
+/// Record a failed token request.
+pub(crate) fn token_failed(_err: &TokenError) {
+  #[cfg(feature = "tracing")]
+  let reason = match _err {
+    TokenError::AccessDenied(description) => description.as_str(),
+    TokenError::Transport(_) => "transport error",
+    TokenError::Key(_) => "invalid private key",
+    _ => "token error",
+  };
+
+  #[cfg(feature = "tracing")]
+  tracing::warn!(reason, "access token refresh failed");
+}
+
+/// Record a failed token cache read or write.
+pub(crate) fn token_cache_failed(_err: &TokenError) {
+  #[cfg(feature = "tracing")]
+  tracing::warn!(error = %_err, "access token cache failed");
+}
@@ -0,0 +1,277 @@
+//! Record and replay requests for offline tests.
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use http::header::{HeaderName, HeaderValue};
+use http::{HeaderMap, StatusCode};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::redact::{scrub, SECRET_HEADERS};
+use crate::{HttpBody, HttpError, HttpRequest, HttpResponse, HttpTransport};
+
+/// Whether a [Cassette] records or replays requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+  /// Send requests and write them with their responses to the cassette, replacing it.
+  Record,
+  /// Answer requests from the cassette without sending them.
+  Replay,
+  /// Replay if the cassette exists, record otherwise.
+  Auto,
+}
+
+/// [HttpTransport] recording requests and responses to a file and replaying them.
+///
+/// Requests are matched on method, path, query and body.  Query parameters are compared
+/// regardless of order and bodies as JSON documents.  Bearer tokens aren't recorded, access
+/// tokens, client secrets, client assertions and passwords are replaced with `[REDACTED]`.
+///
+/// ```no_run
+/// use auth0_management::{Auth0, CassetteMode};
+///
+/// let auth0 = Auth0::builder()
+///   .domain("example.auth0.com")
+///   .client_id("CLIENT_ID")
+///   .client_secret("CLIENT_SECRET")
+///   .cassette("tests/cassettes/find_users.json", CassetteMode::Auto)
+///   .build()
+///   .unwrap();
+/// ```
+pub struct Cassette {
+  path: PathBuf,
+  transport: Option<Arc<dyn HttpTransport>>,
+  interactions: Mutex<Vec<Interaction>>,
+  used: Mutex<Vec<bool>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CassetteFile {
+  interactions: Vec<Interaction>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Interaction {
+  request: RecordedRequest,
+  response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct RecordedRequest {
+  method: String,
+  path: String,
+  #[serde(default)]
+  query: BTreeMap<String, Vec<String>>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  body: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct RecordedResponse {
+  status: u16,
+  #[serde(default)]
+  headers: Vec<(String, String)>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  body: Option<Value>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  text: Option<String>,
+}
+
+impl Cassette {
+  /// Create cassette sending requests with `transport` and recording them to `path`.
+  pub fn record<P: AsRef<Path>>(path: P, transport: Arc<dyn HttpTransport>) -> Self {
+    Self {
+      path: path.as_ref().to_owned(),
+      transport: Some(transport),
+      interactions: Mutex::new(Vec::new()),
+      used: Mutex::new(Vec::new()),
+    }
+  }
+
+  /// Create cassette answering requests from the cassette at `path`.
+  pub fn replay<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+    let file: CassetteFile = serde_json::from_slice(&fs::read(path.as_ref())?)?;
+
+    Ok(Self {
+      path: path.as_ref().to_owned(),
+      transport: None,
+      used: Mutex::new(vec![false; file.interactions.len()]),
+      interactions: Mutex::new(file.interactions),
+    })
+  }
+
+  /// Create cassette at `path` in `mode`, `transport` sends recorded requests.
+  pub fn new<P: AsRef<Path>>(
+    path: P,
+    mode: CassetteMode,
+    transport: Arc<dyn HttpTransport>,
+  ) -> io::Result<Self> {
+    match mode {
+      CassetteMode::Record => Ok(Self::record(path, transport)),
+      CassetteMode::Replay => Self::replay(path),
+      CassetteMode::Auto if path.as_ref().exists() => Self::replay(path),
+      CassetteMode::Auto => Ok(Self::record(path, transport)),
+    }
+  }
+
+  /// Gets boolean determining if requests are recorded.
+  pub fn is_recording(&self) -> bool {
+    self.transport.is_some()
+  }
+
+  /// Write the recorded interactions to the cassette file.
+  fn save(&self, interactions: &[Interaction]) -> io::Result<()> {
+    if let Some(dir) = self.path.parent() {
+      fs::create_dir_all(dir)?;
+    }
+
+    let file = CassetteFile {
+      interactions: interactions.to_vec(),
+    };
+
+    fs::write(&self.path, serde_json::to_vec_pretty(&file)?)
+  }
+
+  /// Find the recorded response of `req`, interactions are replayed in order and the last
+  /// match is repeated once all matches were used.
+  fn find(&self, req: &RecordedRequest) -> Option<RecordedResponse> {
+    let interactions = self.interactions.lock().unwrap();
+    let mut used = self.used.lock().unwrap();
+    let matches = interactions
+      .iter()
+      .enumerate()
+      .filter(|(_, interaction)| &interaction.request == req)
+      .map(|(i, _)| i)
+      .collect::<Vec<_>>();
+    let i = matches
+      .iter()
+      .find(|i| !used[**i])
+      .or_else(|| matches.last())?;
+
+    used[*i] = true;
+    Some(interactions[*i].response.clone())
+  }
+}
+
+#[async_trait]
+impl HttpTransport for Cassette {
+  async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+    let recorded = RecordedRequest::from_request(&req)?;
+    let transport = match &self.transport {
+      Some(transport) => transport,
+      None => {
+        return match self.find(&recorded) {
+          Some(res) => res.into_response(),
+          None => Err(HttpError::new(format!(
+            "no interaction recorded for {} {} in {}",
+            recorded.method,
+            recorded.path,
+            self.path.display()
+          ))),
+        }
+      }
+    };
+
+    let res = transport.send(req).await?;
+    let mut interactions = self.interactions.lock().unwrap();
+    interactions.push(Interaction {
+      request: recorded,
+      response: RecordedResponse::from_response(&res),
+    });
+    self.save(&interactions).map_err(HttpError::new)?;
+
+    Ok(res)
+  }
+}
+
+impl Debug for Cassette {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Cassette")
+      .field("path", &self.path)
+      .field("recording", &self.is_recording())
+      .finish()
+  }
+}
+
+impl RecordedRequest {
+  fn from_request(req: &HttpRequest) -> Result<Self, HttpError> {
+    let url = Url::parse(&req.url).map_err(HttpError::builder)?;
+    let mut query = BTreeMap::<String, Vec<String>>::new();
+    let pairs = url
+      .query_pairs()
+      .map(|(key, value)| (key.into_owned(), value.into_owned()))
+      .chain(req.query.iter().cloned());
+    for (key, value) in pairs {
+      query.entry(key).or_default().push(value);
+    }
+    let body = match &req.body {
+      Some(HttpBody::Json(json)) => Some(json.clone()),
+      Some(HttpBody::Form(form)) => Some(Value::Object(
+        form
+          .iter()
+          .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+          .collect(),
+      )),
+      None => None,
+    };
+
+    Ok(Self {
+      method: req.method.to_string(),
+      path: url.path().to_owned(),
+      query,
+      body: body.map(scrub),
+    })
+  }
+}
+
+impl RecordedResponse {
+  fn from_response(res: &HttpResponse) -> Self {
+    let headers = res
+      .headers
+      .iter()
+      .filter(|(name, _)| !SECRET_HEADERS.contains(&name.as_str()))
+      .filter_map(|(name, value)| {
+        Some((name.to_string(), value.to_str().ok()?.to_owned()))
+      })
+      .collect();
+    let (body, text) = match serde_json::from_slice::<Value>(&res.body) {
+      Ok(body) => (Some(scrub(body)), None),
+      Err(_) if res.body.is_empty() => (None, None),
+      Err(_) => (None, Some(String::from_utf8_lossy(&res.body).into_owned())),
+    };
+
+    Self {
+      status: res.status.as_u16(),
+      headers,
+      body,
+      text,
+    }
+  }
+
+  fn into_response(self) -> Result<HttpResponse, HttpError> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in self.headers {
+      headers.append(
+        HeaderName::from_bytes(name.as_bytes()).map_err(HttpError::new)?,
+        HeaderValue::from_str(&value).map_err(HttpError::new)?,
+      );
+    }
+    let body = match (self.body, self.text) {
+      (Some(body), _) => serde_json::to_vec(&body).map_err(HttpError::new)?,
+      (None, Some(text)) => text.into_bytes(),
+      (None, None) => Vec::new(),
+    };
+
+    Ok(HttpResponse {
+      status: StatusCode::from_u16(self.status).map_err(HttpError::new)?,
+      headers,
+      body,
+    })
+  }
+}
@@ -0,0 +1,252 @@
+//! Transport sending requests to the tenant.
+//!
+//! Request builders describe requests with [HttpRequest], an [HttpTransport] sends them.
+//! [ReqwestTransport] is used unless another transport is set with
+//! [Auth0Builder::transport](crate::Auth0Builder::transport).
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use http::header::{HeaderValue, AUTHORIZATION};
+use http::{HeaderMap, Method, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+
+pub use cassette::*;
+pub use reqwest_transport::*;
+
+pub mod cassette;
+pub(crate) mod redact;
+pub mod reqwest_transport;
+
+/// Sends requests described by [HttpRequest].
+///
+/// ```
+/// use async_trait::async_trait;
+/// use auth0_management::{HttpError, HttpRequest, HttpResponse, HttpTransport};
+///
+/// struct Offline;
+///
+/// #[async_trait]
+/// impl HttpTransport for Offline {
+///   async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+///     Err(HttpError::connect(format!("offline, can't reach {}", req.url)))
+///   }
+/// }
+/// ```
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+  /// Send `req` and read the complete response.
+  async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError>;
+}
+
+/// Body of a request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HttpBody {
+  /// JSON document, sent as `application/json`.
+  Json(Value),
+  /// Form fields, sent as `application/x-www-form-urlencoded`.
+  Form(Vec<(String, String)>),
+}
+
+/// Transport independent description of a request.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+  /// Method of the request.
+  pub method: Method,
+  /// Url of the request without query.
+  pub url: String,
+  /// Query parameters in order.
+  pub query: Vec<(String, String)>,
+  /// Headers of the request.
+  pub headers: HeaderMap,
+  /// Body of the request.
+  pub body: Option<HttpBody>,
+  /// Time the request may take, `None` to use the timeout of the transport.
+  pub timeout: Option<Duration>,
+
+  error: Option<String>,
+}
+
+impl HttpRequest {
+  /// Create request without query, headers or body.
+  pub fn new<S: Into<String>>(method: Method, url: S) -> Self {
+    Self {
+      method,
+      url: url.into(),
+      query: Vec::new(),
+      headers: HeaderMap::new(),
+      body: None,
+      timeout: None,
+      error: None,
+    }
+  }
+
+  /// Append the fields of `query` to the query parameters.
+  pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+    match url_encoded(query) {
+      Ok(query) => self.query.extend(query),
+      Err(err) => self.error = Some(err),
+    }
+    self
+  }
+
+  /// Set JSON body.
+  pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+    match serde_json::to_value(json) {
+      Ok(json) => self.body = Some(HttpBody::Json(json)),
+      Err(err) => self.error = Some(err.to_string()),
+    }
+    self
+  }
+
+  /// Set form body.
+  pub fn form<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+    match url_encoded(form) {
+      Ok(form) => self.body = Some(HttpBody::Form(form)),
+      Err(err) => self.error = Some(err),
+    }
+    self
+  }
+
+  /// Set the `Authorization` header to the bearer `token`.
+  pub fn bearer_auth(mut self, token: &str) -> Self {
+    match HeaderValue::from_str(&format!("Bearer {}", token)) {
+      Ok(mut value) => {
+        value.set_sensitive(true);
+        self.headers.insert(AUTHORIZATION, value);
+      }
+      Err(err) => self.error = Some(err.to_string()),
+    }
+    self
+  }
+
+  /// Set time the request may take.
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Get the JSON body, if any.
+  pub fn json_body(&self) -> Option<&Value> {
+    match &self.body {
+      Some(HttpBody::Json(json)) => Some(json),
+      _ => None,
+    }
+  }
+
+  /// Fail when sent with `error`, unless building the request failed before.
+  pub(crate) fn error(mut self, error: String) -> Self {
+    self.error.get_or_insert(error);
+    self
+  }
+
+  /// Fail with the first error building the request, e.g. a body which couldn't be
+  /// serialized.
+  pub(crate) fn validate(&self) -> Result<(), HttpError> {
+    match &self.error {
+      Some(err) => Err(HttpError::builder(err.clone())),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Get fields of `value` as they are url encoded.
+fn url_encoded<T: Serialize + ?Sized>(
+  value: &T,
+) -> Result<Vec<(String, String)>, String> {
+  let encoded = serde_urlencoded::to_string(value).map_err(|err| err.to_string())?;
+
+  serde_urlencoded::from_str(&encoded).map_err(|err| err.to_string())
+}
+
+/// Response read by an [HttpTransport].
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+  /// Status of the response.
+  pub status: StatusCode,
+  /// Headers of the response.
+  pub headers: HeaderMap,
+  /// Body of the response.
+  pub body: Vec<u8>,
+}
+
+/// Error sending a request.
+#[derive(Debug)]
+pub struct HttpError {
+  kind: HttpErrorKind,
+  source: Box<dyn Error + Send + Sync>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HttpErrorKind {
+  Builder,
+  Connect,
+  Timeout,
+  Other,
+}
+
+impl HttpError {
+  /// Create error for a request which failed for any other reason.
+  pub fn new<E: Into<Box<dyn Error + Send + Sync>>>(source: E) -> Self {
+    Self::with_kind(HttpErrorKind::Other, source)
+  }
+
+  /// Create error for a request which couldn't be built.
+  pub fn builder<E: Into<Box<dyn Error + Send + Sync>>>(source: E) -> Self {
+    Self::with_kind(HttpErrorKind::Builder, source)
+  }
+
+  /// Create error for a connection to the tenant which failed.
+  pub fn connect<E: Into<Box<dyn Error + Send + Sync>>>(source: E) -> Self {
+    Self::with_kind(HttpErrorKind::Connect, source)
+  }
+
+  /// Create error for a request which timed out.
+  pub fn timeout<E: Into<Box<dyn Error + Send + Sync>>>(source: E) -> Self {
+    Self::with_kind(HttpErrorKind::Timeout, source)
+  }
+
+  fn with_kind<E: Into<Box<dyn Error + Send + Sync>>>(
+    kind: HttpErrorKind,
+    source: E,
+  ) -> Self {
+    Self {
+      kind,
+      source: source.into(),
+    }
+  }
+
+  /// Gets boolean determining if the request couldn't be built.
+  pub fn is_builder(&self) -> bool {
+    self.kind == HttpErrorKind::Builder
+  }
+
+  /// Gets boolean determining if the connection failed.
+  pub fn is_connect(&self) -> bool {
+    self.kind == HttpErrorKind::Connect
+  }
+
+  /// Gets boolean determining if the request timed out.
+  pub fn is_timeout(&self) -> bool {
+    self.kind == HttpErrorKind::Timeout
+  }
+
+  /// Get the underlying error, e.g. a [reqwest::Error] for [ReqwestTransport].
+  pub fn get_ref(&self) -> &(dyn Error + Send + Sync + 'static) {
+    &*self.source
+  }
+}
+
+impl Display for HttpError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+impl Error for HttpError {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(&*self.source)
+  }
+}
@@ -0,0 +1,91 @@
+//! Redaction of secrets in requests and responses handed to middleware and cassettes.
+use std::borrow::Cow;
+
+use http::{HeaderMap, HeaderValue};
+use serde_json::Value;
+
+use crate::HttpBody;
+
+/// Value replacing secrets.
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// Body and form fields holding secrets.
+pub(crate) const SECRET_FIELDS: &[&str] = &[
+  "access_token",
+  "id_token",
+  "refresh_token",
+  "client_secret",
+  "client_assertion",
+  "password",
+];
+
+/// Headers holding secrets.
+pub(crate) const SECRET_HEADERS: &[&str] = &["authorization", "set-cookie"];
+
+/// Replace the values of secret fields in `value`.
+pub(crate) fn scrub(value: Value) -> Value {
+  match value {
+    Value::Object(map) => Value::Object(
+      map
+        .into_iter()
+        .map(|(key, value)| {
+          if SECRET_FIELDS.contains(&key.as_str()) {
+            (key, Value::String(REDACTED.to_owned()))
+          } else {
+            (key, scrub(value))
+          }
+        })
+        .collect(),
+    ),
+    Value::Array(values) => Value::Array(values.into_iter().map(scrub).collect()),
+    value => value,
+  }
+}
+
+/// Copy `headers` with the values of secret headers replaced.
+pub(crate) fn redact_headers(headers: &HeaderMap) -> HeaderMap {
+  let mut headers = headers.clone();
+  for name in SECRET_HEADERS {
+    if headers.contains_key(*name) {
+      headers.insert(*name, HeaderValue::from_static(REDACTED));
+    }
+  }
+  headers
+}
+
+/// Copy `body` with the values of secret fields replaced.
+pub(crate) fn redact_body(body: &HttpBody) -> HttpBody {
+  match body {
+    HttpBody::Json(json) => HttpBody::Json(scrub(json.clone())),
+    HttpBody::Form(form) => HttpBody::Form(
+      form
+        .iter()
+        .map(|(key, value)| {
+          if SECRET_FIELDS.contains(&key.as_str()) {
+            (key.clone(), REDACTED.to_owned())
+          } else {
+            (key.clone(), value.clone())
+          }
+        })
+        .collect(),
+    ),
+  }
+}
+
+/// Get `body` with the values of secret fields replaced, bodies without secret fields are
+/// returned as they are.
+pub(crate) fn redact_response_body(body: &[u8]) -> Cow<'_, [u8]> {
+  let has_secret = SECRET_FIELDS.iter().any(|field| {
+    body
+      .windows(field.len())
+      .any(|window| window == field.as_bytes())
+  });
+  if !has_secret {
+    return Cow::Borrowed(body);
+  }
+
+  match serde_json::from_slice::<Value>(body) {
+    Ok(json) => Cow::Owned(serde_json::to_vec(&scrub(json)).unwrap_or_default()),
+    Err(_) => Cow::Borrowed(REDACTED.as_bytes()),
+  }
+}
@@ -0,0 +1,71 @@
+//! Transport sending requests with reqwest.
+use async_trait::async_trait;
+use reqwest::Client;
+
+use crate::{HttpBody, HttpError, HttpRequest, HttpResponse, HttpTransport};
+
+/// [HttpTransport] sending requests with a [reqwest::Client].
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+  client: Client,
+}
+
+impl ReqwestTransport {
+  /// Create transport sending requests with `client`.
+  pub fn new(client: Client) -> Self {
+    Self { client }
+  }
+}
+
+impl From<Client> for ReqwestTransport {
+  fn from(client: Client) -> Self {
+    Self::new(client)
+  }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+  async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+    let mut builder = self
+      .client
+      .request(req.method, &req.url)
+      .headers(req.headers);
+
+    if !req.query.is_empty() {
+      builder = builder.query(&req.query);
+    }
+    builder = match &req.body {
+      Some(HttpBody::Json(json)) => builder.json(json),
+      Some(HttpBody::Form(form)) => builder.form(form),
+      None => builder,
+    };
+    if let Some(timeout) = req.timeout {
+      builder = builder.timeout(timeout);
+    }
+
+    let res = builder.send().await?;
+    let status = res.status();
+    let headers = res.headers().clone();
+    let body = res.bytes().await?.to_vec();
+
+    Ok(HttpResponse {
+      status,
+      headers,
+      body,
+    })
+  }
+}
+
+impl From<reqwest::Error> for HttpError {
+  fn from(err: reqwest::Error) -> Self {
+    if err.is_timeout() {
+      HttpError::timeout(err)
+    } else if err.is_connect() {
+      HttpError::connect(err)
+    } else if err.is_builder() {
+      HttpError::builder(err)
+    } else {
+      HttpError::new(err)
+    }
+  }
+}
@@ -0,0 +1,101 @@
+#![warn(missing_docs)]
+//! User block request builders.
+
+#[doc(inline)]
+pub use user_blocks_delete::*;
+#[doc(inline)]
+pub use user_blocks_get::*;
+
+use crate::{Auth0Client, Auth0RequestSimple, Auth0Result, UserId};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub mod user_blocks_delete;
+pub mod user_blocks_get;
+
+/// Blocked identifier and ip address pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBlock {
+  /// Identifier (email, username or phone number) that is blocked.
+  pub identifier: String,
+  /// IP address of the identifier that is blocked.
+  pub ip: Option<String>,
+}
+
+/// List of blocks for a user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBlocks {
+  /// Array of identifier + IP address pairs.  IP address is optional, and may be omitted
+  /// in certain circumstances (such as Account Lockout mode).
+  pub blocked_for: Vec<UserBlock>,
+}
+
+/// User blocks manager
+pub struct UserBlocksManager(Arc<Auth0Client>);
+
+impl UserBlocksManager {
+  /// Create user blocks manager
+  pub fn new(client: Arc<Auth0Client>) -> Self {
+    Self(client)
+  }
+
+  /// Retrieve a list of blocked IP addresses for a given user.
+  ///
+  /// # Arguments
+  /// * `id` - The id of the user.
+  /// # Scopes
+  /// * `read:users`
+  pub async fn get<I: Into<UserId>>(&self, id: I) -> Auth0Result<Vec<UserBlock>> {
+    let blocks: UserBlocks = UserBlocksGet::new(id).send_to(&self.0).await?;
+
+    Ok(blocks.blocked_for)
+  }
+
+  /// Retrieve a list of blocked IP addresses for the login identifiers (email, username,
+  /// phone number, etc) associated with the specified identifier.
+  ///
+  /// # Arguments
+  /// * `identifier` - Should be any of a username, phone number, or email.
+  /// # Scopes
+  /// * `read:users`
+  pub async fn get_by_identifier<S: AsRef<str>>(
+    &self,
+    identifier: S,
+  ) -> Auth0Result<Vec<UserBlock>> {
+    let blocks: UserBlocks = UserBlocksGetByIdentifier::new(identifier)
+      .send_to(&self.0)
+      .await?;
+
+    Ok(blocks.blocked_for)
+  }
+
+  /// Remove all [Brute-force Protection](https://auth0.com/docs/attack-protection/brute-force-protection)
+  /// blocks for the user with the given ID.
+  ///
+  /// # Arguments
+  /// * `id` - The id of the user to unblock.
+  /// # Scopes
+  /// * `update:users`
+  pub async fn delete<I: Into<UserId>>(&self, id: I) -> Auth0Result<()> {
+    UserBlocksDelete::new(id).send_to(&self.0).await
+  }
+
+  /// Remove all [Brute-force Protection](https://auth0.com/docs/attack-protection/brute-force-protection)
+  /// blocks for the user with the given identifier (username, phone number, or email).
+  ///
+  /// Note: This endpoint does not unblock users that were
+  /// [blocked by an administrator](https://auth0.com/docs/users/block-and-unblock-users).
+  ///
+  /// # Arguments
+  /// * `identifier` - Should be any of a username, phone number, or email.
+  /// # Scopes
+  /// * `update:users`
+  pub async fn delete_by_identifier<S: AsRef<str>>(
+    &self,
+    identifier: S,
+  ) -> Auth0Result<()> {
+    UserBlocksDeleteByIdentifier::new(identifier)
+      .send_to(&self.0)
+      .await
+  }
+}
@@ -0,0 +1,90 @@
+//! Remove brute-force protection blocks for a user.
+use http::Method;
+
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new("api/v2/user-blocks/{id}", &["update:users"]);
+const BY_IDENTIFIER_ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/user-blocks", &["update:users"]);
+
+/// Remove all brute-force protection blocks for the user with the given ID.
+///
+/// # Scopes
+/// * `update:users`
+pub struct UserBlocksDelete {
+  id: UserId,
+  options: RequestOptions,
+}
+
+impl UserBlocksDelete {
+  /// Create delete user blocks request.
+  pub fn new<I: Into<UserId>>(id: I) -> Self {
+    Self {
+      id: id.into(),
+      options: Default::default(),
+    }
+  }
+}
+
+impl Auth0RequestBuilder for UserBlocksDelete {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self.options.apply(client.begin_endpoint(
+      Method::DELETE,
+      &ENDPOINT,
+      &[self.id.as_str()],
+    ))
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+/// Remove all brute-force protection blocks for the user with the given identifier
+/// (username, phone number, or email).
+///
+/// # Scopes
+/// * `update:users`
+pub struct UserBlocksDeleteByIdentifier {
+  identifier: String,
+  options: RequestOptions,
+}
+
+impl UserBlocksDeleteByIdentifier {
+  /// Create delete user blocks request.
+  /// # Arguments
+  /// * `identifier` - Should be any of a username, phone number, or email.
+  pub fn new<S: AsRef<str>>(identifier: S) -> Self {
+    Self {
+      identifier: identifier.as_ref().to_owned(),
+      options: Default::default(),
+    }
+  }
+}
+
+impl Auth0RequestBuilder for UserBlocksDeleteByIdentifier {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::DELETE, BY_IDENTIFIER_ENDPOINT.path))
+      .query(&[("identifier", &self.identifier)])
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &BY_IDENTIFIER_ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserBlocksDelete {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl AsMut<RequestOptions> for UserBlocksDeleteByIdentifier {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
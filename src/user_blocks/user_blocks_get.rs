@@ -0,0 +1,88 @@
+//! Retrieve a list of blocked IP addresses for a user.
+use http::Method;
+
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new("api/v2/user-blocks/{id}", &["read:users"]);
+const BY_IDENTIFIER_ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/user-blocks", &["read:users"]);
+
+/// Retrieve a list of blocked IP addresses for a given user.
+///
+/// # Scopes
+/// * `read:users`
+pub struct UserBlocksGet {
+  id: UserId,
+  options: RequestOptions,
+}
+
+impl UserBlocksGet {
+  /// Create get user blocks request.
+  pub fn new<I: Into<UserId>>(id: I) -> Self {
+    Self {
+      id: id.into(),
+      options: Default::default(),
+    }
+  }
+}
+
+impl Auth0RequestBuilder for UserBlocksGet {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::GET, &ENDPOINT, &[self.id.as_str()]))
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+/// Retrieve a list of blocked IP addresses for the login identifiers (email, username,
+/// phone number, etc) associated with the specified identifier.
+///
+/// # Scopes
+/// * `read:users`
+pub struct UserBlocksGetByIdentifier {
+  identifier: String,
+  options: RequestOptions,
+}
+
+impl UserBlocksGetByIdentifier {
+  /// Create get user blocks request.
+  /// # Arguments
+  /// * `identifier` - Should be any of a username, phone number, or email.
+  pub fn new<S: AsRef<str>>(identifier: S) -> Self {
+    Self {
+      identifier: identifier.as_ref().to_owned(),
+      options: Default::default(),
+    }
+  }
+}
+
+impl Auth0RequestBuilder for UserBlocksGetByIdentifier {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::GET, BY_IDENTIFIER_ENDPOINT.path))
+      .query(&[("identifier", &self.identifier)])
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &BY_IDENTIFIER_ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserBlocksGet {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl AsMut<RequestOptions> for UserBlocksGetByIdentifier {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
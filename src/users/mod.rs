@@ -4,6 +4,8 @@
 #[doc(inline)]
 pub use permissions::*;
 #[doc(inline)]
+pub use roles::*;
+#[doc(inline)]
 pub use user::*;
 #[doc(inline)]
 pub use user_create::*;
@@ -16,31 +18,43 @@ pub use user_get::*;
 #[doc(inline)]
 pub use user_logs_get::*;
 #[doc(inline)]
+pub use user_metadata_patch::*;
+#[doc(inline)]
 pub use user_update::*;
 #[doc(inline)]
 pub use users_find::*;
+#[doc(inline)]
+pub use users_find_by_email::*;
 
-use crate::{Auth0Client, Auth0Error, Auth0RequestSimple, Auth0Result};
+use crate::{Auth0Client, Auth0Error, Auth0RequestSimple, Auth0Result, UserId};
 use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 pub mod permissions;
+pub mod roles;
 pub mod user;
 pub mod user_create;
 pub mod user_delete;
 pub mod user_enrollments_get;
 pub mod user_get;
 pub mod user_logs_get;
+pub mod user_metadata_patch;
 pub mod user_update;
 pub mod users_find;
+pub mod users_find_by_email;
 
-/// Users manager
-pub struct UsersManager(Arc<Auth0Client>);
+/// Users manager, reading and writing users with `A` app metadata and `U` user metadata.
+pub struct UsersManager<A = Value, U = Value>(
+  Arc<Auth0Client>,
+  PhantomData<fn() -> (A, U)>,
+);
 
-impl UsersManager {
+impl<A, U> UsersManager<A, U> {
   /// Create users manager
   pub fn new(client: Arc<Auth0Client>) -> Self {
-    Self(client)
+    Self(client, PhantomData)
   }
 
   /// Create a new user for a given [database](https://auth0.com/docs/connections/database)
@@ -51,8 +65,8 @@ impl UsersManager {
   ///
   /// # Scopes
   /// * `create:users`
-  pub fn create(&self) -> UserCreate<'_, (), ()> {
-    UserCreate::new(&self.0)
+  pub fn create(&self) -> UserCreate<A, U> {
+    UserCreate::typed(self.0.clone())
   }
 
   /// Delete a user.
@@ -61,7 +75,7 @@ impl UsersManager {
   /// * `id` - The id of the user to delete.
   /// # Scopes
   /// * `delete:users`
-  pub async fn delete<S: AsRef<str>>(&self, id: S) -> Auth0Result<()> {
+  pub async fn delete<I: Into<UserId>>(&self, id: I) -> Auth0Result<()> {
     UserDelete::new(id).send_to(&self.0).await
   }
 
@@ -71,9 +85,9 @@ impl UsersManager {
   ///
   /// # Scopes
   /// * `read:users`
-  pub async fn get_enrollments<S: AsRef<str>>(
+  pub async fn get_enrollments<I: Into<UserId>>(
     &self,
-    id: S,
+    id: I,
   ) -> Auth0Result<Vec<UserEnrollment>> {
     UserEnrollmentsGet::new(id).send_to(&self.0).await
   }
@@ -85,7 +99,7 @@ impl UsersManager {
   /// # Scopes
   /// * `read:users`
   /// * `read:user_idp_tokens`
-  pub async fn get<A, U, S: AsRef<str>>(&self, id: S) -> Result<User<A, U>, Auth0Error>
+  pub async fn get<I: Into<UserId>>(&self, id: I) -> Result<User<A, U>, Auth0Error>
   where
     A: DeserializeOwned + Send + Sync,
     U: DeserializeOwned + Send + Sync,
@@ -110,8 +124,35 @@ impl UsersManager {
   /// # Scopes
   /// * `read:logs`
   /// * `read:logs_users`
-  pub fn get_logs<S: AsRef<str>>(&self, id: S) -> UserLogsGet<'_> {
-    UserLogsGet::new(&self.0, id)
+  pub fn get_logs<I: Into<UserId>>(&self, id: I) -> UserLogsGet {
+    UserLogsGet::new(self.0.clone(), id)
+  }
+
+  /// Retrieve the roles assigned to a user.
+  ///
+  /// # Scopes
+  /// * `read:users`
+  /// * `read:roles`
+  pub fn get_roles<I: Into<UserId>>(&self, id: I) -> UserRolesGet {
+    UserRolesGet::new(self.0.clone(), id)
+  }
+
+  /// Assign roles to a user.
+  ///
+  /// # Scopes
+  /// * `update:users`
+  /// * `create:role_members`
+  pub fn assign_roles<I: Into<UserId>>(&self, id: I) -> UserRolesAssign {
+    UserRolesAssign::new(self.0.clone(), id)
+  }
+
+  /// Remove roles from a user.
+  ///
+  /// # Scopes
+  /// * `update:users`
+  /// * `delete:role_members`
+  pub fn remove_roles<I: Into<UserId>>(&self, id: I) -> UserRolesRemove {
+    UserRolesRemove::new(self.0.clone(), id)
   }
 
   /// Update a user.
@@ -119,24 +160,40 @@ impl UsersManager {
   ///
   /// * The properties of the new object will replace the old ones.
   /// * The metadata fields are an exception to this rule (`user_metadata` and
-  /// `app_metadata`). These properties are merged instead of being replaced but be
-  /// careful, the merge only occurs on the first level.
+  ///   `app_metadata`). These properties are merged instead of being replaced but be
+  ///   careful, the merge only occurs on the first level.
   /// * If you are updating `email`, `email_verified`, `phone_number`, `phone_verified`,
-  /// `username` or `password` of a secondary identity, you need to specify the connection
-  /// property too.
+  ///   `username` or `password` of a secondary identity, you need to specify the connection
+  ///   property too.
   /// * If you are updating `email` or `phone_number` you can specify, optionally, the
-  /// `client_id` property.
+  ///   `client_id` property.
   /// * Updating `email_verified` is not supported for enterprise and passwordless sms
-  /// connections.
+  ///   connections.
   /// * Updating the `blocked` to `false` does not affect the user's blocked state from an
-  /// excessive amount of incorrectly provided credentials. Use the "Unblock a user"
-  /// endpoint from the "User Blocks" API to change the user's state.
+  ///   excessive amount of incorrectly provided credentials. Use the "Unblock a user"
+  ///   endpoint from the "User Blocks" API to change the user's state.
   ///
   /// # Scopes
   /// * `update:users`
   /// * `update:users_app_metadata`
-  pub fn update<S: AsRef<str>>(&self, id: S) -> UserUpdate<'_, (), ()> {
-    UserUpdate::new(&self.0, id)
+  pub fn update<I: Into<UserId>>(&self, id: I) -> UserUpdate<A, U> {
+    UserUpdate::typed(self.0.clone(), id)
+  }
+
+  /// Apply [MetadataPatch] changes to the `app_metadata` and `user_metadata` of a user.
+  ///
+  /// Unlike [UsersManager::update] nested values can be changed without replacing sibling
+  /// keys.  The user is read, patched and read again, if a concurrent write changed any of
+  /// the first level keys written the patches are reapplied.
+  ///
+  /// # Arguments
+  /// * `id` - The id of the user to patch.
+  /// # Scopes
+  /// * `read:users`
+  /// * `update:users`
+  /// * `update:users_app_metadata`
+  pub fn patch_metadata<I: Into<UserId>>(&self, id: I) -> UserMetadataPatch<A, U> {
+    UserMetadataPatch::typed(self.0.clone(), id)
   }
 
   /// Retrieve details of users.
@@ -163,7 +220,26 @@ impl UsersManager {
   /// # Scopes
   /// * `read:users`
   /// * `read:user_idp_tokens`
-  pub fn find(&self) -> UsersFind<'_> {
-    UsersFind::new(&self.0)
+  pub fn find(&self) -> UsersFind<A, U> {
+    UsersFind::typed(self.0.clone())
+  }
+
+  /// Find users by email.  If Auth0 is the identity provider (idP), the email address
+  /// associated with a user is saved in lower case, regardless of how you initially provided
+  /// it.
+  ///
+  /// For example, if you register a user as JohnSmith@example.com, Auth0 saves the user's
+  /// email as johnsmith@example.com.
+  ///
+  /// Therefore, when using this endpoint, make sure that you are searching for users via
+  /// email addresses using the correct case, or see [UsersFindByEmail::case_insensitive] to
+  /// find users saved in lower case too.
+  ///
+  /// # Arguments
+  /// * `email` - The email address to search for (case-sensitive).
+  /// # Scopes
+  /// * `read:users`
+  pub fn find_by_email<S: AsRef<str>>(&self, email: S) -> UsersFindByEmail<A, U> {
+    UsersFindByEmail::typed(self.0.clone(), email)
   }
 }
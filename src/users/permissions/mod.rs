@@ -22,3 +22,9 @@ pub struct Permission {
   /// Resource server (API) identifier that this permission is for.
   pub resource_server_identifier: String,
 }
+
+/// Body of requests assigning or removing permissions.
+#[derive(Serialize)]
+pub(crate) struct PermissionsBody<'a> {
+  pub permissions: &'a [Permission],
+}
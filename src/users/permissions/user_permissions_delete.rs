@@ -1,32 +1,44 @@
 //! Remove permissions from a user.
+use std::future::IntoFuture;
+use std::sync::Arc;
 
-use reqwest::{Method, RequestBuilder};
+use http::Method;
 
+use crate::users::permissions::PermissionsBody;
 use crate::Permission;
-use crate::{Auth0Client, Auth0RequestBuilder};
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users/{id}/permissions", &["update:users"]);
 
 /// Provides data for creating delete user permission request.
 ///
 /// # Scopes
 /// * `update:users`
-pub struct UserPermissionsDelete<'a> {
-  client: &'a Auth0Client,
+#[derive(Clone)]
+pub struct UserPermissionsDelete {
+  client: Arc<Auth0Client>,
 
-  id: String,
+  id: UserId,
   permissions: Vec<Permission>,
+  options: RequestOptions,
 }
 
-impl<'a> UserPermissionsDelete<'a> {
+impl UserPermissionsDelete {
   /// Create permission delete request.
   ///
   /// # Arguments
   /// * `id` - The id of the user.
-  pub fn new(client: &'a Auth0Client, id: &str) -> Self {
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
     Self {
       client,
 
-      id: id.to_owned(),
+      id: id.into(),
       permissions: Vec::new(),
+      options: Default::default(),
     }
   }
 
@@ -49,19 +61,47 @@ impl<'a> UserPermissionsDelete<'a> {
   }
 }
 
-impl<'a> AsRef<Auth0Client> for UserPermissionsDelete<'a> {
+impl AsRef<Auth0Client> for UserPermissionsDelete {
   fn as_ref(&self) -> &Auth0Client {
-    self.client
+    &self.client
+  }
+}
+
+impl Auth0RequestBuilder for UserPermissionsDelete {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::DELETE, &ENDPOINT, &[self.id.as_str()]))
+      .json(&PermissionsBody {
+        permissions: &self.permissions,
+      })
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
   }
 }
 
-impl<'a> Auth0RequestBuilder for UserPermissionsDelete<'a> {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client
-      .begin(
-        Method::DELETE,
-        &format!("api/v2/users/{}/permissions", self.id),
-      )
-      .json(&self.permissions)
+impl AsMut<RequestOptions> for UserPermissionsDelete {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for UserPermissionsDelete {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserPermissionsDelete {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
   }
 }
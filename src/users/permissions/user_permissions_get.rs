@@ -1,8 +1,17 @@
 //! Retrieve all permissions associated with the user.
-use reqwest::{Method, RequestBuilder};
+use std::future::IntoFuture;
+use std::sync::Arc;
 
-use crate::Page;
-use crate::{Auth0Client, Auth0RequestBuilder};
+use http::Method;
+
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+use crate::{Page, Permission};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users/{id}/permissions", &["read:users"]);
 
 /// Provides data for get user permissions request.
 ///
@@ -13,44 +22,73 @@ use crate::{Auth0Client, Auth0RequestBuilder};
 /// ```
 /// async fn dump_permissions() {}
 /// ```
-pub struct UserPermissionsGet<'a> {
-  client: &'a Auth0Client,
+#[derive(Clone)]
+pub struct UserPermissionsGet {
+  client: Arc<Auth0Client>,
 
-  id: String,
+  id: UserId,
   page: Page,
+  options: RequestOptions,
 }
 
-impl<'a> UserPermissionsGet<'a> {
+impl UserPermissionsGet {
   /// Create get user permissions request.
-  pub fn new(client: &'a Auth0Client, id: &str) -> Self {
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
     Self {
       client,
 
-      id: id.to_owned(),
+      id: id.into(),
       page: Default::default(),
+      options: Default::default(),
     }
   }
 }
 
-impl<'a> AsMut<Page> for UserPermissionsGet<'a> {
+impl AsMut<Page> for UserPermissionsGet {
   fn as_mut(&mut self) -> &mut Page {
     &mut self.page
   }
 }
 
-impl<'a> AsRef<Auth0Client> for UserPermissionsGet<'a> {
+impl AsRef<Auth0Client> for UserPermissionsGet {
   fn as_ref(&self) -> &Auth0Client {
-    self.client
+    &self.client
   }
 }
 
-impl<'a> Auth0RequestBuilder for UserPermissionsGet<'a> {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client
-      .begin(
-        Method::GET,
-        &format!("api/v2/users/{}/permissions", self.id),
-      )
+impl Auth0RequestBuilder for UserPermissionsGet {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::GET, &ENDPOINT, &[self.id.as_str()]))
       .query(&self.page)
   }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserPermissionsGet {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for UserPermissionsGet {
+  type Output = Auth0Result<Vec<Permission>>;
+  type IntoFuture = SendFuture<Vec<Permission>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserPermissionsGet {
+  type Output = Auth0Result<Vec<Permission>>;
+  type IntoFuture = SendFuture<Vec<Permission>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
 }
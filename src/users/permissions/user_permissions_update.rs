@@ -1,9 +1,18 @@
 //! Assign permissions to a user.
+use std::future::IntoFuture;
+use std::sync::Arc;
 
-use reqwest::{Method, RequestBuilder};
+use http::Method;
 
+use crate::users::permissions::PermissionsBody;
 use crate::Permission;
-use crate::{Auth0Client, Auth0RequestBuilder};
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users/{id}/permissions", &["update:users"]);
 
 /// Assign user permissions.
 ///
@@ -14,24 +23,27 @@ use crate::{Auth0Client, Auth0RequestBuilder};
 /// ```
 /// async fn add_permission() {}
 /// ```
-pub struct UserPermissionsUpdate<'a> {
-  client: &'a Auth0Client,
+#[derive(Clone)]
+pub struct UserPermissionsUpdate {
+  client: Arc<Auth0Client>,
 
-  id: String,
+  id: UserId,
   permissions: Vec<Permission>,
+  options: RequestOptions,
 }
 
-impl<'a> UserPermissionsUpdate<'a> {
+impl UserPermissionsUpdate {
   /// Create assign user permissions request.
   ///
   /// # Arguments
   /// * `id` - The user id.
-  pub fn new(client: &'a Auth0Client, id: &str) -> Self {
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
     Self {
       client,
 
-      id: id.to_owned(),
+      id: id.into(),
       permissions: Vec::new(),
+      options: Default::default(),
     }
   }
 
@@ -54,64 +66,47 @@ impl<'a> UserPermissionsUpdate<'a> {
   }
 }
 
-impl<'a> AsRef<Auth0Client> for UserPermissionsUpdate<'a> {
+impl AsRef<Auth0Client> for UserPermissionsUpdate {
   fn as_ref(&self) -> &Auth0Client {
-    self.client
+    &self.client
+  }
+}
+
+impl Auth0RequestBuilder for UserPermissionsUpdate {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::POST, &ENDPOINT, &[self.id.as_str()]))
+      .json(&PermissionsBody {
+        permissions: &self.permissions,
+      })
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
   }
 }
 
-impl<'a> Auth0RequestBuilder for UserPermissionsUpdate<'a> {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client
-      .begin(
-        Method::POST,
-        &format!("api/v2/users/{}/permissions", self.id),
-      )
-      .json(&self.permissions)
+impl AsMut<RequestOptions> for UserPermissionsUpdate {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
   }
 }
 
-// #[cfg(test)]
-// mod tests {
-//   use crate::Auth0RequestBuilder;
-//   use crate::{Permission, UserPermissionsUpdate};
-//   use reqwest::Client;
-//
-//   #[test]
-//   fn test_create() {
-//     let req = UserPermissionsUpdate::new("USER_ID")
-//       .permission(Permission {
-//         name: "test1".to_string(),
-//         description: "test1".to_string(),
-//         resource_server_name: "test1".to_string(),
-//         resource_server_identifier: "test1".to_string(),
-//       })
-//       .permissions(&[
-//         Permission {
-//           name: "test2".to_string(),
-//           description: "test2".to_string(),
-//           resource_server_name: "test2".to_string(),
-//           resource_server_identifier: "test2".to_string(),
-//         },
-//         Permission {
-//           name: "test3".to_string(),
-//           description: "test3".to_string(),
-//           resource_server_name: "test3".to_string(),
-//           resource_server_identifier: "test3".to_string(),
-//         },
-//       ])
-//       .build(|method, path| {
-//         Client::new().request(method, &format!("https://ipsum/{}", path))
-//       })
-//       .build()
-//       .unwrap();
-//
-//     let body: Vec<Permission> =
-//       serde_json::from_reader(req.body().unwrap().as_bytes().unwrap()).unwrap();
-//
-//     assert_eq!(req.url().path(), "/api/v2/users/USER_ID/permissions");
-//     assert_eq!(body.len(), 3);
-//     assert_eq!(body.first().unwrap().name, "test1");
-//     assert_eq!(body.last().unwrap().name, "test3");
-//   }
-// }
+impl IntoFuture for UserPermissionsUpdate {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserPermissionsUpdate {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -0,0 +1,30 @@
+//! User role request builders.
+use serde::{Deserialize, Serialize};
+
+pub use user_roles_assign::*;
+pub use user_roles_get::*;
+pub use user_roles_remove::*;
+
+use crate::RoleId;
+
+pub mod user_roles_assign;
+pub mod user_roles_get;
+pub mod user_roles_remove;
+
+/// Role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+  /// ID of this role.
+  pub id: RoleId,
+  /// Name of this role.
+  pub name: String,
+  /// Description of this role.
+  #[serde(default)]
+  pub description: String,
+}
+
+/// Body of requests assigning or removing roles.
+#[derive(Serialize)]
+pub(crate) struct RolesBody<'a> {
+  pub roles: &'a [RoleId],
+}
@@ -0,0 +1,107 @@
+//! Assign roles to a user.
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
+
+use crate::users::roles::RolesBody;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, RoleId, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+
+const ENDPOINT: Endpoint = Endpoint::new(
+  "api/v2/users/{id}/roles",
+  &["update:users", "create:role_members"],
+);
+
+/// Assign user roles.
+///
+/// # Scopes
+/// * `update:users`
+/// * `create:role_members`
+#[derive(Clone)]
+pub struct UserRolesAssign {
+  client: Arc<Auth0Client>,
+
+  id: UserId,
+  roles: Vec<RoleId>,
+  options: RequestOptions,
+}
+
+impl UserRolesAssign {
+  /// Create assign user roles request.
+  ///
+  /// # Arguments
+  /// * `id` - The user id.
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
+    Self {
+      client,
+
+      id: id.into(),
+      roles: Vec::new(),
+      options: Default::default(),
+    }
+  }
+
+  /// Add role to assign.
+  ///
+  /// # Arguments
+  /// * `role` - The id of the role.
+  pub fn role<I: Into<RoleId>>(&mut self, role: I) -> &mut Self {
+    self.roles.push(role.into());
+    self
+  }
+
+  /// Add multiple roles to assign.
+  ///
+  /// # Arguments
+  /// * `roles` - The ids of the roles.
+  pub fn roles<R: AsRef<[RoleId]>>(&mut self, roles: R) -> &mut Self {
+    self.roles.extend_from_slice(roles.as_ref());
+    self
+  }
+}
+
+impl AsRef<Auth0Client> for UserRolesAssign {
+  fn as_ref(&self) -> &Auth0Client {
+    &self.client
+  }
+}
+
+impl Auth0RequestBuilder for UserRolesAssign {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::POST, &ENDPOINT, &[self.id.as_str()]))
+      .json(&RolesBody { roles: &self.roles })
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserRolesAssign {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for UserRolesAssign {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserRolesAssign {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -0,0 +1,90 @@
+//! Retrieve all roles associated with the user.
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
+
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+use crate::{Page, Role};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users/{id}/roles", &["read:users", "read:roles"]);
+
+/// Provides data for get user roles request.
+///
+/// # Scopes
+/// * `read:users`
+/// * `read:roles`
+#[derive(Clone)]
+pub struct UserRolesGet {
+  client: Arc<Auth0Client>,
+
+  id: UserId,
+  page: Page,
+  options: RequestOptions,
+}
+
+impl UserRolesGet {
+  /// Create get user roles request.
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
+    Self {
+      client,
+
+      id: id.into(),
+      page: Default::default(),
+      options: Default::default(),
+    }
+  }
+}
+
+impl AsMut<Page> for UserRolesGet {
+  fn as_mut(&mut self) -> &mut Page {
+    &mut self.page
+  }
+}
+
+impl AsRef<Auth0Client> for UserRolesGet {
+  fn as_ref(&self) -> &Auth0Client {
+    &self.client
+  }
+}
+
+impl Auth0RequestBuilder for UserRolesGet {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::GET, &ENDPOINT, &[self.id.as_str()]))
+      .query(&self.page)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserRolesGet {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for UserRolesGet {
+  type Output = Auth0Result<Vec<Role>>;
+  type IntoFuture = SendFuture<Vec<Role>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserRolesGet {
+  type Output = Auth0Result<Vec<Role>>;
+  type IntoFuture = SendFuture<Vec<Role>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
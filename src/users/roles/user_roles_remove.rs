@@ -0,0 +1,107 @@
+//! Remove roles from a user.
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
+
+use crate::users::roles::RolesBody;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, RoleId, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+
+const ENDPOINT: Endpoint = Endpoint::new(
+  "api/v2/users/{id}/roles",
+  &["update:users", "delete:role_members"],
+);
+
+/// Remove user roles.
+///
+/// # Scopes
+/// * `update:users`
+/// * `delete:role_members`
+#[derive(Clone)]
+pub struct UserRolesRemove {
+  client: Arc<Auth0Client>,
+
+  id: UserId,
+  roles: Vec<RoleId>,
+  options: RequestOptions,
+}
+
+impl UserRolesRemove {
+  /// Create remove user roles request.
+  ///
+  /// # Arguments
+  /// * `id` - The user id.
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
+    Self {
+      client,
+
+      id: id.into(),
+      roles: Vec::new(),
+      options: Default::default(),
+    }
+  }
+
+  /// Add role to remove.
+  ///
+  /// # Arguments
+  /// * `role` - The id of the role.
+  pub fn role<I: Into<RoleId>>(&mut self, role: I) -> &mut Self {
+    self.roles.push(role.into());
+    self
+  }
+
+  /// Add multiple roles to remove.
+  ///
+  /// # Arguments
+  /// * `roles` - The ids of the roles.
+  pub fn roles<R: AsRef<[RoleId]>>(&mut self, roles: R) -> &mut Self {
+    self.roles.extend_from_slice(roles.as_ref());
+    self
+  }
+}
+
+impl AsRef<Auth0Client> for UserRolesRemove {
+  fn as_ref(&self) -> &Auth0Client {
+    &self.client
+  }
+}
+
+impl Auth0RequestBuilder for UserRolesRemove {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::DELETE, &ENDPOINT, &[self.id.as_str()]))
+      .json(&RolesBody { roles: &self.roles })
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserRolesRemove {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for UserRolesRemove {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserRolesRemove {
+  type Output = Auth0Result<()>;
+  type IntoFuture = SendFuture<()>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -1,64 +1,283 @@
 //! User
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Number, Value};
+
+use crate::UserId;
 
 /// User identity.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct Identity {
-  provider: String,
+  /// Name of the connection containing this identity.
+  pub connection: String,
+  /// Unique identifier of the user for this identity.
+  pub user_id: IdentityUserId,
+  /// The type of identity provider.
+  pub provider: String,
+  /// Whether this identity is from a social provider (true) or not (false).
   #[serde(rename = "isSocial")]
-  is_social: bool,
-  connection: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub is_social: Option<bool>,
+  /// IDP access token returned only if scope `read:user_idp_tokens` is defined.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub access_token: Option<String>,
+  /// IDP access token secret returned only if scope `read:user_idp_tokens` is defined.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub access_token_secret: Option<String>,
+  /// IDP refresh token returned only if scope `read:user_idp_tokens` is defined.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub refresh_token: Option<String>,
+  /// Profile of the user from a linked (secondary) identity.
+  #[serde(rename = "profileData")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub profile_data: Option<Value>,
+
+  /// Identity attributes not covered by the fields above.
+  #[serde(flatten)]
+  pub extra: HashMap<String, Value>,
 }
 
 /// User
-#[derive(Deserialize, Debug)]
+///
+/// Only `user_id` is required, every other attribute may be missing depending on the
+/// connection type (e.g. SMS passwordless users have no email) or on the fields requested.
+/// Attributes not covered by the fields below are kept in [User::extra] so a user can be
+/// serialized back without losing data.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(bound(
+  deserialize = "AppMetadata: Deserialize<'de>, UserMetadata: Deserialize<'de>"
+))]
 pub struct User<AppMetadata, UserMetadata> {
   /// ID of the user which can be used when interacting with other APIs.
-  pub user_id: String,
+  pub user_id: UserId,
   /// Email address of this user.
-  pub email: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub email: Option<String>,
   /// Whether this email address is verified (true) or unverified (false).
-  pub email_verified: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub email_verified: Option<bool>,
   /// Username of this user.
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub username: Option<String>,
   /// Phone number for this user when using SMS connections.
   /// [Follows the E.164 recommendation.](https://en.wikipedia.org/wiki/E.164)
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub phone_number: Option<String>,
   /// Whether this phone number has been verified (true) or not (false).
-  #[serde(default)]
-  pub phone_verified: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub phone_verified: Option<bool>,
   /// Date and time when this user was created.
-  pub created_at: DateTime<Utc>,
+  #[serde(skip_serializing_if = "Option::is_none", serialize_with = "timestamp")]
+  pub created_at: Option<DateTime<Utc>>,
   /// Date and time when this user was last updated/modified.
-  pub updated_at: DateTime<Utc>,
+  #[serde(skip_serializing_if = "Option::is_none", serialize_with = "timestamp")]
+  pub updated_at: Option<DateTime<Utc>>,
   /// Array of user identity objects when accounts are linked.
-  pub identities: Vec<Identity>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub identities: Option<Vec<Identity>>,
   /// URL to picture, photo, or avatar of this user.
-  pub picture: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub picture: Option<String>,
   /// Name of this user.
-  pub name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
   /// Preferred nickname or alias of this user.
-  pub nickname: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub nickname: Option<String>,
 
   /// List of multi-factor authentication providers with which this user has enrolled.
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub multifactor: Option<Vec<String>>,
   /// Last IP address from which this user logged in.
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub last_ip: Option<String>,
   /// Last date and time this user logged in.
+  #[serde(skip_serializing_if = "Option::is_none", serialize_with = "timestamp")]
   pub last_login: Option<DateTime<Utc>>,
+  /// Last date and time this user reset their password.
+  #[serde(skip_serializing_if = "Option::is_none", serialize_with = "timestamp")]
+  pub last_password_reset: Option<DateTime<Utc>>,
   /// Total number of logins this user has performed.
-  #[serde(default)]
-  pub logins_count: u32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub logins_count: Option<u32>,
   /// Whether this user was blocked by an administrator (true) or is not (false).
-  #[serde(default)]
-  pub blocked: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub blocked: Option<bool>,
   /// Given name/first name/forename of this user.
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub given_name: Option<String>,
   /// Family name/last name/surname of this user.
+  #[serde(skip_serializing_if = "Option::is_none")]
   pub family_name: Option<String>,
-  /// User metadata to which this user has read-only access.
-  pub app_metadata: Option<AppMetadata>,
-  /// User metadata to which this user has read/write access.
-  pub user_metadata: Option<UserMetadata>,
+  /// User metadata to which this user has read-only access, `Some(None)` when sent as
+  /// `null`.
+  #[serde(default, deserialize_with = "nullable")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub app_metadata: Option<Option<AppMetadata>>,
+  /// User metadata to which this user has read/write access, `Some(None)` when sent as
+  /// `null`.
+  #[serde(default, deserialize_with = "nullable")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub user_metadata: Option<Option<UserMetadata>>,
+
+  /// User attributes not covered by the fields above.
+  #[serde(flatten)]
+  pub extra: HashMap<String, Value>,
+}
+
+impl<AppMetadata, UserMetadata> User<AppMetadata, UserMetadata> {
+  /// Get metadata to which this user has read-only access, missing and `null` metadata alike
+  /// are `None`.
+  pub fn app_metadata(&self) -> Option<&AppMetadata> {
+    self.app_metadata.as_ref().and_then(Option::as_ref)
+  }
+
+  /// Get metadata to which this user has read/write access, missing and `null` metadata
+  /// alike are `None`.
+  pub fn user_metadata(&self) -> Option<&UserMetadata> {
+    self.user_metadata.as_ref().and_then(Option::as_ref)
+  }
+}
+
+/// Unique identifier of a user at an identity provider, kept as sent since some providers
+/// (e.g. github) send numbers.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum IdentityUserId {
+  /// Identifier sent as a string.
+  String(String),
+  /// Identifier sent as a number.
+  Number(Number),
+}
+
+impl Display for IdentityUserId {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::String(id) => f.write_str(id),
+      Self::Number(id) => Display::fmt(id, f),
+    }
+  }
+}
+
+impl From<String> for IdentityUserId {
+  fn from(id: String) -> Self {
+    Self::String(id)
+  }
+}
+
+impl From<&str> for IdentityUserId {
+  fn from(id: &str) -> Self {
+    Self::String(id.to_owned())
+  }
+}
+
+impl From<u64> for IdentityUserId {
+  fn from(id: u64) -> Self {
+    Self::Number(id.into())
+  }
+}
+
+impl PartialEq<str> for IdentityUserId {
+  fn eq(&self, other: &str) -> bool {
+    match self {
+      Self::String(id) => id == other,
+      Self::Number(id) => id.to_string() == other,
+    }
+  }
+}
+
+impl PartialEq<&str> for IdentityUserId {
+  fn eq(&self, other: &&str) -> bool {
+    self == *other
+  }
+}
+
+/// Deserializes values which are present, `null` included, to `Some` so they can be told
+/// apart from missing values.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de>,
+{
+  Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Serializes timestamps with milliseconds like Auth0 does.
+fn timestamp<S: Serializer>(
+  value: &Option<DateTime<Utc>>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  match value {
+    Some(value) => {
+      serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+    None => serializer.serialize_none(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::{json, Value};
+
+  use crate::{IdentityUserId, User};
+
+  #[test]
+  fn test_deserialize_sms_user() {
+    let user: User<Value, Value> = serde_json::from_value(json!({
+      "user_id": "sms|5f5b5d5e5f5b5d5e5f5b5d5e",
+      "phone_number": "+15555550100",
+      "phone_verified": true,
+      "created_at": "2020-09-11T12:00:00.000Z",
+      "updated_at": "2020-09-11T12:00:00.000Z",
+      "identities": [{
+        "connection": "sms",
+        "user_id": "5f5b5d5e5f5b5d5e5f5b5d5e",
+        "provider": "sms",
+        "isSocial": false
+      }]
+    }))
+    .unwrap();
+
+    assert_eq!(user.email, None);
+    assert_eq!(user.phone_number.as_deref(), Some("+15555550100"));
+    assert_eq!(user.identities.as_ref().unwrap()[0].provider, "sms");
+    assert_eq!(user.user_metadata, None);
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let raw = json!({
+      "user_id": "github|1234",
+      "email": "test@example.test",
+      "email_verified": false,
+      "identities": [{
+        "connection": "github",
+        "user_id": 1234,
+        "provider": "github",
+        "isSocial": true
+      }],
+      "logins_count": 3,
+      "app_metadata": { "plan": "free" },
+      "locale": "en",
+      "custom_claim": { "nested": [1, 2, 3] }
+    });
+
+    let user: User<Value, Value> = serde_json::from_value(raw.clone()).unwrap();
+    let identities = user.identities.as_ref().unwrap();
+    assert_eq!(identities[0].user_id, "1234");
+    assert_eq!(user.extra["locale"], "en");
+
+    assert_eq!(identities[0].user_id, IdentityUserId::from(1234));
+
+    let serialized = serde_json::to_value(&user).unwrap();
+    let reparsed: User<Value, Value> =
+      serde_json::from_value(serialized.clone()).unwrap();
+
+    assert_eq!(reparsed, user);
+    assert_eq!(serialized, raw);
+    assert_eq!(serialized.get("phone_number"), None);
+  }
 }
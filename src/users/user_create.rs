@@ -1,18 +1,42 @@
 //! Create a new user.
-use reqwest::Method;
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::users::User;
-use crate::{Auth0Client, Auth0Result};
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Auth0Result, Endpoint, HttpRequest, RequestOptions,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new("api/v2/users", &["create:users"]);
 
 /// Create a new user for a given [database](https://auth0.com/docs/connections/database) or
 /// [passwordless](https://auth0.com/docs/connections/passwordless) connection.
 #[derive(Serialize, Clone, Debug)]
-pub struct UserCreate<'a, A, U> {
+pub struct UserCreate<A = Value, U = Value> {
   #[serde(skip_serializing)]
-  client: &'a Auth0Client,
+  client: Arc<Auth0Client>,
+
+  #[serde(flatten)]
+  fields: UserCreateFields,
 
+  #[serde(skip_serializing_if = "Option::is_none")]
+  app_metadata: Option<A>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_metadata: Option<U>,
+
+  #[serde(skip)]
+  options: RequestOptions,
+}
+
+/// Attributes of a [UserCreate] request other than the metadata.
+#[derive(Serialize, Clone, Debug, Default)]
+struct UserCreateFields {
   #[serde(skip_serializing_if = "Option::is_none")]
   email: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -43,195 +67,159 @@ pub struct UserCreate<'a, A, U> {
   verify_email: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   username: Option<String>,
-
-  #[serde(skip_serializing_if = "Option::is_none")]
-  app_metadata: Option<A>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  user_metadata: Option<U>,
 }
 
-impl<'a> UserCreate<'a, (), ()> {
+impl UserCreate {
   /// Create create user request.
-  pub fn new(client: &'a Auth0Client) -> Self {
+  pub fn new(client: Arc<Auth0Client>) -> Self {
+    Self::typed(client)
+  }
+}
+
+impl<A, U> UserCreate<A, U> {
+  /// Create create user request with `A` and `U` metadata.
+  pub(crate) fn typed(client: Arc<Auth0Client>) -> Self {
     Self {
       client,
 
-      email: None,
-      phone_number: None,
-      blocked: None,
-      email_verified: None,
-      phone_verified: None,
-      given_name: None,
-      family_name: None,
-      name: None,
-      nickname: None,
-      picture: None,
-      user_id: None,
-      connection: None,
-      password: None,
-      verify_email: None,
-      username: None,
-
+      fields: Default::default(),
       app_metadata: None,
       user_metadata: None,
+      options: Default::default(),
     }
   }
-}
 
-impl<'a, A, U> UserCreate<'a, A, U>
-where
-  A: Clone,
-  U: Clone,
-{
   /// The user's email.
   pub fn email(&mut self, email: &str) -> &mut Self {
-    self.email = Some(email.to_owned());
+    self.fields.email = Some(email.to_owned());
     self
   }
 
   /// The user's phone number (following the E.164 recommendation), only valid for users from SMS
   /// connections.
   pub fn phone_number(&mut self, phone_number: &str) -> &mut Self {
-    self.phone_number = Some(phone_number.to_owned());
+    self.fields.phone_number = Some(phone_number.to_owned());
     self
   }
 
   /// Whether this user was blocked by an administrator (true) or not (false).
   pub fn blocked(&mut self, blocked: bool) -> &mut Self {
-    self.blocked = Some(blocked);
+    self.fields.blocked = Some(blocked);
     self
   }
 
   /// Whether this email address is verified (true) or unverified (false). User will receive a
   /// verification email after creation if email_verified is false or not specified.
   pub fn email_verified(&mut self, email_verified: bool) -> &mut Self {
-    self.email_verified = Some(email_verified);
+    self.fields.email_verified = Some(email_verified);
     self
   }
 
   /// Whether this phone number has been verified (true) or not (false).
   pub fn phone_verified(&mut self, phone_verified: bool) -> &mut Self {
-    self.phone_verified = Some(phone_verified);
+    self.fields.phone_verified = Some(phone_verified);
     self
   }
 
   /// The user's given name(s).
   pub fn given_name(&mut self, given_name: &str) -> &mut Self {
-    self.given_name = Some(given_name.to_owned());
+    self.fields.given_name = Some(given_name.to_owned());
     self
   }
 
   /// The user's family name(s).
   pub fn family_name(&mut self, family_name: &str) -> &mut Self {
-    self.family_name = Some(family_name.to_owned());
+    self.fields.family_name = Some(family_name.to_owned());
     self
   }
 
   /// The user's full name.
   pub fn name(&mut self, name: &str) -> &mut Self {
-    self.name = Some(name.to_owned());
+    self.fields.name = Some(name.to_owned());
     self
   }
 
   /// The user's nickname.
   pub fn nickname(&mut self, nickname: &str) -> &mut Self {
-    self.nickname = Some(nickname.to_owned());
+    self.fields.nickname = Some(nickname.to_owned());
     self
   }
 
   /// A URI pointing to the user's picture.
   pub fn picture(&mut self, picture: &str) -> &mut Self {
-    self.picture = Some(picture.to_owned());
+    self.fields.picture = Some(picture.to_owned());
     self
   }
 
   /// The external user's id provided by the identity provider.
   pub fn user_id(&mut self, user_id: &str) -> &mut Self {
-    self.user_id = Some(user_id.to_owned());
+    self.fields.user_id = Some(user_id.to_owned());
     self
   }
 
   /// Name of the connection this user should be created in.
   pub fn connection(&mut self, connection: &str) -> &mut Self {
-    self.connection = Some(connection.to_owned());
+    self.fields.connection = Some(connection.to_owned());
     self
   }
 
   /// Initial password for this user (mandatory for non-SMS connections).
   pub fn password(&mut self, password: &str) -> &mut Self {
-    self.password = Some(password.to_owned());
+    self.fields.password = Some(password.to_owned());
     self
   }
 
   /// Whether the user will receive a verification email after creation (true) or no email (false).
   /// Overrides behavior of email_verified parameter.
   pub fn verify_email(&mut self, verify_email: bool) -> &mut Self {
-    self.verify_email = Some(verify_email);
+    self.fields.verify_email = Some(verify_email);
     self
   }
 
   /// The user's username. Only valid if the connection requires a username.
   pub fn username(&mut self, username: &str) -> &mut Self {
-    self.username = Some(username.to_owned());
+    self.fields.username = Some(username.to_owned());
     self
   }
 
   /// Data related to the user that does affect the application's core functionality.
-  pub fn app_metadata<AppMetadata: Clone>(
-    &mut self,
-    app_metadata: AppMetadata,
-  ) -> UserCreate<'a, AppMetadata, U> {
+  pub fn app_metadata(&mut self, app_metadata: A) -> &mut Self {
+    self.app_metadata = Some(app_metadata);
+    self
+  }
+
+  /// Data related to the user that does not affect the application's core functionality.
+  pub fn user_metadata(&mut self, user_metadata: U) -> &mut Self {
+    self.user_metadata = Some(user_metadata);
+    self
+  }
+
+  /// Set the `app_metadata` to a value of another type, e.g. a typed struct instead of
+  /// [Value].
+  pub fn with_app_metadata<T>(self, app_metadata: T) -> UserCreate<T, U> {
     UserCreate {
       client: self.client,
-      email: self.email.clone(),
-      phone_number: self.phone_number.clone(),
-      blocked: self.blocked,
-      email_verified: self.email_verified,
-      phone_verified: self.phone_verified,
-      given_name: self.given_name.clone(),
-      family_name: self.family_name.clone(),
-      name: self.name.clone(),
-      nickname: self.nickname.clone(),
-      picture: self.picture.clone(),
-      user_id: self.user_id.clone(),
-      connection: self.connection.clone(),
-      password: self.password.clone(),
-      verify_email: self.verify_email,
-      username: self.username.clone(),
+      fields: self.fields,
       app_metadata: Some(app_metadata),
-      user_metadata: self.user_metadata.clone(),
+      user_metadata: self.user_metadata,
+      options: self.options,
     }
   }
 
-  /// Data related to the user that does not affect the application's core functionality.
-  pub fn user_metadata<UserMetadata: Clone>(
-    &mut self,
-    user_metadata: UserMetadata,
-  ) -> UserCreate<'a, A, UserMetadata> {
+  /// Set the `user_metadata` to a value of another type, e.g. a typed struct instead of
+  /// [Value].
+  pub fn with_user_metadata<T>(self, user_metadata: T) -> UserCreate<A, T> {
     UserCreate {
       client: self.client,
-      email: self.email.clone(),
-      phone_number: self.phone_number.clone(),
-      blocked: self.blocked,
-      email_verified: self.email_verified,
-      phone_verified: self.phone_verified,
-      given_name: self.given_name.clone(),
-      family_name: self.family_name.clone(),
-      name: self.name.clone(),
-      nickname: self.nickname.clone(),
-      picture: self.picture.clone(),
-      user_id: self.user_id.clone(),
-      connection: self.connection.clone(),
-      password: self.password.clone(),
-      verify_email: self.verify_email,
-      username: self.username.clone(),
-      app_metadata: self.app_metadata.clone(),
+      fields: self.fields,
+      app_metadata: self.app_metadata,
       user_metadata: Some(user_metadata),
+      options: self.options,
     }
   }
 }
 
-impl<'a, AIn, UIn> UserCreate<'a, AIn, UIn> {
+impl<AIn, UIn> UserCreate<AIn, UIn> {
   /// Send
   pub async fn send<AOut, UOut>(&self) -> Auth0Result<User<AOut, UOut>>
   where
@@ -242,7 +230,52 @@ impl<'a, AIn, UIn> UserCreate<'a, AIn, UIn> {
   {
     self
       .client
-      .send(self.client.begin(Method::POST, "api/v2/users").json(self))
+      .send_endpoint(self.build(&self.client), &ENDPOINT)
       .await
   }
 }
+
+impl<A: Serialize, U: Serialize> Auth0RequestBuilder for UserCreate<A, U> {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::POST, ENDPOINT.path))
+      .json(self)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl<A, U> AsMut<RequestOptions> for UserCreate<A, U> {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl<A, U> IntoFuture for UserCreate<A, U>
+where
+  A: Serialize + DeserializeOwned + Send + Sync + 'static,
+  U: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<User<A, U>>;
+  type IntoFuture = SendFuture<User<A, U>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send::<A, U>().await })
+  }
+}
+
+impl<A, U> IntoFuture for &mut UserCreate<A, U>
+where
+  A: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+  U: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+  type Output = Auth0Result<User<A, U>>;
+  type IntoFuture = SendFuture<User<A, U>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -1,23 +1,47 @@
 //! Delete a user.
-use reqwest::{Method, RequestBuilder};
+use http::Method;
 
-use crate::{Auth0Client, Auth0RequestBuilder};
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new("api/v2/users/{id}", &["delete:users"]);
 
 /// Delete a user.
 ///
 /// # Scopes
 /// * `delete:users`
-pub struct UserDelete(String);
+pub struct UserDelete {
+  id: UserId,
+  options: RequestOptions,
+}
 
 impl UserDelete {
   /// Create delete user request.
-  pub fn new<S: AsRef<str>>(id: S) -> Self {
-    Self(id.as_ref().to_string())
+  pub fn new<I: Into<UserId>>(id: I) -> Self {
+    Self {
+      id: id.into(),
+      options: Default::default(),
+    }
   }
 }
 
 impl Auth0RequestBuilder for UserDelete {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client.begin(Method::DELETE, &format!("api/v2/users/{}", self.0))
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self.options.apply(client.begin_endpoint(
+      Method::DELETE,
+      &ENDPOINT,
+      &[self.id.as_str()],
+    ))
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserDelete {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
   }
 }
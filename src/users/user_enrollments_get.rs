@@ -2,10 +2,15 @@
 //! enrollment for a user.
 
 use chrono::{DateTime, Utc};
-use reqwest::{Method, RequestBuilder};
+use http::Method;
 use serde::Deserialize;
 
-use crate::{Auth0Client, Auth0RequestBuilder};
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users/{id}/enrollments", &["read:users"]);
 
 /// Multi-factor enrollment.
 #[derive(Debug, Clone, Deserialize)]
@@ -36,23 +41,34 @@ pub struct UserEnrollment {
 /// .com/docs/multifactor-authentication/guardian)
 /// enrollment for a user.
 pub struct UserEnrollmentsGet {
-  id: String,
+  id: UserId,
+  options: RequestOptions,
 }
 
 impl UserEnrollmentsGet {
   /// Create user enrollments request.
-  pub fn new<S: AsRef<str>>(id: S) -> Self {
+  pub fn new<I: Into<UserId>>(id: I) -> Self {
     Self {
-      id: id.as_ref().to_owned(),
+      id: id.into(),
+      options: Default::default(),
     }
   }
 }
 
 impl Auth0RequestBuilder for UserEnrollmentsGet {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client.begin(
-      Method::GET,
-      &format!("api/v2/users/{}/enrollments", self.id),
-    )
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::GET, &ENDPOINT, &[self.id.as_str()]))
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserEnrollmentsGet {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
   }
 }
@@ -1,33 +1,57 @@
 //! Retrieve user details. A list of fields to include or exclude may also be specified.
-use reqwest::{Method, RequestBuilder};
+use http::Method;
 
-use crate::{Auth0Client, Auth0RequestBuilder};
+use crate::users::users_find_by_email;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Endpoint, HttpRequest, RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new("api/v2/users/{id}", &["read:users"]);
 
 /// Retrieve user details. A list of fields to include or exclude may also be specified.
 pub struct UserGet {
-  id: String,
+  id: UserId,
+  options: RequestOptions,
 }
 
 impl UserGet {
   /// Create get user request.
-  pub fn new<S: AsRef<str>>(id: S) -> Self {
+  pub fn new<I: Into<UserId>>(id: I) -> Self {
     Self {
-      id: id.as_ref().to_string(),
+      id: id.into(),
+      options: Default::default(),
     }
   }
 }
 
 impl Auth0RequestBuilder for UserGet {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client.begin(Method::GET, &format!("api/v2/users/{}", self.id))
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::GET, &ENDPOINT, &[self.id.as_str()]))
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserGet {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
   }
 }
 
 /// Provides data used to request user from email field.
+#[deprecated(
+  since = "0.0.5",
+  note = "use `UsersFindByEmail` or `UsersManager::find_by_email`"
+)]
 pub struct GetUserByEmail {
   email: String,
 }
 
+#[allow(deprecated)]
 impl GetUserByEmail {
   /// Create get user request.
   /// # Arguments
@@ -39,10 +63,15 @@ impl GetUserByEmail {
   }
 }
 
+#[allow(deprecated)]
 impl Auth0RequestBuilder for GetUserByEmail {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
     client
-      .begin(Method::GET, "api/v2/users/users-by-email")
-      .query(&[("email", self.email.to_owned())])
+      .begin(Method::GET, users_find_by_email::ENDPOINT.path)
+      .query(&[("email", &self.email)])
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &users_find_by_email::ENDPOINT
   }
 }
@@ -1,11 +1,60 @@
 //! Retrieve log events for a specific user.
+use std::future::IntoFuture;
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
-use reqwest::{Method, RequestBuilder};
+use http::Method;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{Auth0Client, Auth0RequestBuilder};
-use crate::{Page, Sort};
+use crate::{
+  Auth0Client, Auth0RequestBuilder, ClientId, ConnectionId, Endpoint, HttpRequest, LogId,
+  RequestOptions, UserId,
+};
+use crate::{Auth0Request, Auth0Result, SendFuture};
+use crate::{Page, Sort, SortField};
+
+const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users/{id}/logs", &["read:logs", "read:logs_users"]);
+
+/// Fields log events can be sorted by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogSortField {
+  /// `date`
+  Date,
+  /// `type`
+  Kind,
+  /// `description`
+  Description,
+  /// `connection`
+  Connection,
+  /// `client_id`
+  ClientId,
+  /// `client_name`
+  ClientName,
+  /// `ip`
+  Ip,
+  /// `user_id`
+  UserId,
+  /// `user_name`
+  UserName,
+}
+
+impl SortField for LogSortField {
+  fn name(&self) -> &'static str {
+    match self {
+      Self::Date => "date",
+      Self::Kind => "type",
+      Self::Description => "description",
+      Self::Connection => "connection",
+      Self::ClientId => "client_id",
+      Self::ClientName => "client_name",
+      Self::Ip => "ip",
+      Self::UserId => "user_id",
+      Self::UserName => "user_name",
+    }
+  }
+}
 
 /// User log event.
 #[derive(Debug, Deserialize)]
@@ -20,9 +69,9 @@ pub struct UserLog {
   /// Name of the connection the event relates to.
   pub connection: String,
   /// ID of the connection the event relates to.
-  pub connection_id: String,
+  pub connection_id: ConnectionId,
   /// ID of the client (application).
-  pub client_id: String,
+  pub client_id: ClientId,
   /// Name of the client (application).
   pub client_name: String,
   /// IP address of the log event source.
@@ -30,7 +79,7 @@ pub struct UserLog {
   /// Hostname the event applies to.
   pub hostname: Option<String>,
   /// ID of the user involved in the event.
-  pub user_id: String,
+  pub user_id: UserId,
   /// Name of the user involved in the event.
   pub user_name: String,
   /// API audience the event applies to.
@@ -42,7 +91,7 @@ pub struct UserLog {
   /// Type of strategy involved in the event.
   pub strategy_type: String,
   /// Unique ID of the event.
-  pub log_id: String,
+  pub log_id: LogId,
   /// Whether the client was a mobile device (true) or desktop/laptop/server (false).
   #[serde(rename = "isMobile")]
   pub is_mobile: bool,
@@ -79,54 +128,87 @@ pub struct UserLogLocationInfo {
 }
 
 /// Retrieve log events for a specific user.
-#[derive(Serialize)]
-pub struct UserLogsGet<'a> {
+#[derive(Serialize, Clone)]
+pub struct UserLogsGet {
   #[serde(skip_serializing)]
-  client: &'a Auth0Client,
+  client: Arc<Auth0Client>,
 
   #[serde(skip)]
-  id: String,
+  id: UserId,
   #[serde(flatten)]
   page: Page,
   #[serde(skip_serializing_if = "Sort::is_emtpy")]
-  sort: Sort,
+  sort: Sort<LogSortField>,
+
+  #[serde(skip)]
+  options: RequestOptions,
 }
 
-impl<'a> UserLogsGet<'a> {
+impl UserLogsGet {
   /// Create [GetUserLogs] request.
-  pub fn new<S: AsRef<str>>(client: &'a Auth0Client, id: S) -> Self {
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
     Self {
       client,
 
-      id: id.as_ref().to_string(),
+      id: id.into(),
       page: Default::default(),
       sort: Default::default(),
+      options: Default::default(),
     }
   }
 }
 
-impl<'a> AsMut<Page> for UserLogsGet<'a> {
+impl AsMut<Page> for UserLogsGet {
   fn as_mut(&mut self) -> &mut Page {
     &mut self.page
   }
 }
 
-impl<'a> AsMut<Sort> for UserLogsGet<'a> {
-  fn as_mut(&mut self) -> &mut Sort {
+impl AsMut<Sort<LogSortField>> for UserLogsGet {
+  fn as_mut(&mut self) -> &mut Sort<LogSortField> {
     &mut self.sort
   }
 }
 
-impl<'a> AsRef<Auth0Client> for UserLogsGet<'a> {
+impl AsRef<Auth0Client> for UserLogsGet {
   fn as_ref(&self) -> &Auth0Client {
-    self.client
+    &self.client
   }
 }
 
-impl<'a> Auth0RequestBuilder for UserLogsGet<'a> {
-  fn build(&self, client: &Auth0Client) -> RequestBuilder {
-    client
-      .begin(Method::GET, &format!("api/v2/users/{}/logs", self.id))
+impl Auth0RequestBuilder for UserLogsGet {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::GET, &ENDPOINT, &[self.id.as_str()]))
       .query(&self)
   }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl AsMut<RequestOptions> for UserLogsGet {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl IntoFuture for UserLogsGet {
+  type Output = Auth0Result<Vec<UserLog>>;
+  type IntoFuture = SendFuture<Vec<UserLog>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { Auth0Request::send(&self).await })
+  }
+}
+
+impl IntoFuture for &mut UserLogsGet {
+  type Output = Auth0Result<Vec<UserLog>>;
+  type IntoFuture = SendFuture<Vec<UserLog>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
 }
@@ -0,0 +1,429 @@
+//! Patch `app_metadata` and `user_metadata` of a user.
+//!
+//! Auth0 merges metadata on the first level only and removes keys which are set to `null`.
+//! [MetadataPatch] records the intended changes and compiles them to a payload with those
+//! semantics, nested paths are read from the current metadata, modified and written back
+//! as a whole first level key.
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0Error, Auth0RequestSimple, Auth0Result, RequestOptions, UserId,
+};
+use crate::{User, UserGet, UserUpdate};
+
+/// Set of changes to apply to `app_metadata` or `user_metadata`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetadataPatch {
+  ops: Vec<(Vec<String>, Option<Value>)>,
+}
+
+impl MetadataPatch {
+  /// Create empty metadata patch.
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Determines if patch is empty.
+  pub fn is_empty(&self) -> bool {
+    self.ops.is_empty()
+  }
+
+  /// Set first level key to value.
+  pub fn set<V: Into<Value>>(&mut self, key: &str, value: V) -> &mut Self {
+    self.ops.push((vec![key.to_owned()], Some(value.into())));
+    self
+  }
+
+  /// Delete first level key.
+  pub fn remove(&mut self, key: &str) -> &mut Self {
+    self.ops.push((vec![key.to_owned()], None));
+    self
+  }
+
+  /// Set nested value, missing objects along the path are created.  An empty path is
+  /// ignored.
+  ///
+  /// # Arguments
+  /// * `path` - The keys leading to the value, starting with the first level key.
+  /// * `value` - The value to set.
+  pub fn set_path<V: Into<Value>>(&mut self, path: &[&str], value: V) -> &mut Self {
+    self.push_path(path, Some(value.into()))
+  }
+
+  /// Delete nested value, nothing is changed if the value doesn't exist.  An empty path is
+  /// ignored.
+  ///
+  /// # Arguments
+  /// * `path` - The keys leading to the value, starting with the first level key.
+  pub fn remove_path(&mut self, path: &[&str]) -> &mut Self {
+    self.push_path(path, None)
+  }
+
+  fn push_path(&mut self, path: &[&str], value: Option<Value>) -> &mut Self {
+    if path.is_empty() {
+      return self;
+    }
+
+    self
+      .ops
+      .push((path.iter().map(|key| (*key).to_owned()).collect(), value));
+    self
+  }
+
+  /// Compile patch to a metadata payload suitable for [UserUpdate].
+  ///
+  /// # Arguments
+  /// * `current` - The current metadata of the user, required to apply nested changes.
+  pub fn compile(&self, current: Option<&Value>) -> Value {
+    let mut payload = Map::new();
+
+    for (path, value) in &self.ops {
+      let key = &path[0];
+      if path.len() == 1 {
+        payload.insert(key.clone(), value.clone().unwrap_or(Value::Null));
+        continue;
+      }
+
+      let root = match payload.get(key) {
+        Some(root) => Some(root),
+        None => current.and_then(|current| current.get(key)),
+      };
+      let mut root = match (root, value) {
+        (Some(Value::Object(root)), _) => Value::Object(root.clone()),
+        (_, Some(_)) => Value::Object(Map::new()),
+        // Nothing to remove below a missing or non-object value.
+        (_, None) => continue,
+      };
+
+      apply(&mut root, &path[1..], value.clone());
+      payload.insert(key.clone(), root);
+    }
+
+    Value::Object(payload)
+  }
+
+  /// Determines if the first level keys written by this patch, compiled against `snapshot`,
+  /// are unchanged in `current`, sibling keys included.
+  ///
+  /// # Arguments
+  /// * `snapshot` - The metadata the patch was compiled against before writing.
+  /// * `current` - The metadata read after writing.
+  pub fn is_written(&self, snapshot: Option<&Value>, current: Option<&Value>) -> bool {
+    let payload = self.compile(snapshot);
+    let written = payload.as_object().unwrap();
+
+    written.iter().all(|(key, value)| {
+      let current = current.and_then(|current| current.get(key));
+      current.filter(|current| !current.is_null())
+        == Some(value).filter(|value| !value.is_null())
+    })
+  }
+
+  /// Determines if every change of this patch is reflected in `current`.
+  pub fn is_applied(&self, current: Option<&Value>) -> bool {
+    let empty = Value::Object(Map::new());
+    let current = current.unwrap_or(&empty);
+    let mut expected = current.clone();
+
+    for (path, value) in &self.ops {
+      apply(&mut expected, path, value.clone());
+    }
+
+    self
+      .ops
+      .iter()
+      .all(|(path, _)| lookup(&expected, path) == lookup(current, path))
+  }
+}
+
+/// Set or remove the value at `path` of `target`, replacing non-objects along the way when
+/// setting.
+fn apply(target: &mut Value, path: &[String], value: Option<Value>) {
+  if !target.is_object() {
+    if value.is_none() {
+      return;
+    }
+    *target = Value::Object(Map::new());
+  }
+
+  let map = target.as_object_mut().unwrap();
+  let (key, rest) = path.split_first().unwrap();
+
+  if rest.is_empty() {
+    match value {
+      Some(value) => map.insert(key.clone(), value),
+      None => map.remove(key),
+    };
+  } else if value.is_some() || map.contains_key(key) {
+    apply(map.entry(key.clone()).or_insert(Value::Null), rest, value);
+  }
+}
+
+/// Get the value at `path`, `null` values are considered missing.
+fn lookup<'a>(target: &'a Value, path: &[String]) -> Option<&'a Value> {
+  path
+    .iter()
+    .try_fold(target, |target, key| target.get(key))
+    .filter(|value| !value.is_null())
+}
+
+/// Apply metadata patches to a user with optimistic read-merge-write, returned with `A` and
+/// `U` metadata when awaited.
+///
+/// The user is read, the patches are compiled against the current metadata and written.  The
+/// user is then read again and, if a concurrent write changed any of the first level keys
+/// written, the patches are applied again on top of the fresh metadata.
+///
+/// The API has no conditional updates, a concurrent write landing between the read and the
+/// write to a first level key that is written is overwritten without being detected.
+pub struct UserMetadataPatch<A = Value, U = Value> {
+  client: Arc<Auth0Client>,
+
+  id: UserId,
+  app_metadata: MetadataPatch,
+  user_metadata: MetadataPatch,
+  retries: u32,
+  options: RequestOptions,
+  metadata: PhantomData<fn() -> (A, U)>,
+}
+
+impl UserMetadataPatch {
+  /// Create user metadata patch request.
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
+    Self::typed(client, id)
+  }
+}
+
+impl<A, U> UserMetadataPatch<A, U> {
+  /// Create user metadata patch request with `A` and `U` metadata.
+  pub(crate) fn typed<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
+    Self {
+      client,
+
+      id: id.into(),
+      app_metadata: Default::default(),
+      user_metadata: Default::default(),
+      retries: 3,
+      options: Default::default(),
+      metadata: PhantomData,
+    }
+  }
+
+  /// Changes to apply to the metadata the user has read-only access to.
+  pub fn app_metadata(&mut self, patch: MetadataPatch) -> &mut Self {
+    self.app_metadata = patch;
+    self
+  }
+
+  /// Changes to apply to the metadata the user has read/write access to.
+  pub fn user_metadata(&mut self, patch: MetadataPatch) -> &mut Self {
+    self.user_metadata = patch;
+    self
+  }
+
+  /// Number of times patches are reapplied after a conflicting write.  Defaults to `3`.
+  pub fn retries(&mut self, retries: u32) -> &mut Self {
+    self.retries = retries;
+    self
+  }
+
+  /// Send
+  pub async fn send<AOut, UOut>(&self) -> Auth0Result<User<AOut, UOut>>
+  where
+    AOut: DeserializeOwned,
+    UOut: DeserializeOwned,
+  {
+    let mut user = self.get().await?;
+
+    for _ in 0..=self.retries {
+      self.write(&user).await?;
+
+      let snapshot = user;
+      user = self.get().await?;
+      if self
+        .app_metadata
+        .is_written(snapshot.app_metadata(), user.app_metadata())
+        && self
+          .user_metadata
+          .is_written(snapshot.user_metadata(), user.user_metadata())
+      {
+        return Ok(serde_json::from_value(serde_json::to_value(user)?)?);
+      }
+    }
+
+    Err(Auth0Error::Conflict(self.id.to_string()))
+  }
+
+  async fn get(&self) -> Auth0Result<User<Value, Value>> {
+    let mut get = UserGet::new(&self.id);
+    *get.as_mut() = self.options.clone();
+
+    get.send_to(&self.client).await
+  }
+
+  async fn write(&self, user: &User<Value, Value>) -> Auth0Result<()> {
+    let app_metadata = self.app_metadata.compile(user.app_metadata());
+    let user_metadata = self.user_metadata.compile(user.user_metadata());
+    let mut update = UserUpdate::new(self.client.clone(), &self.id);
+    *update.as_mut() = self.options.clone();
+
+    let _: User<Value, Value> =
+      match (self.app_metadata.is_empty(), self.user_metadata.is_empty()) {
+        (true, true) => return Ok(()),
+        (false, true) => update.app_metadata(app_metadata).send().await?,
+        (true, false) => update.user_metadata(user_metadata).send().await?,
+        (false, false) => {
+          update
+            .app_metadata(app_metadata)
+            .user_metadata(user_metadata)
+            .send()
+            .await?
+        }
+      };
+
+    Ok(())
+  }
+}
+
+impl<A, U> Clone for UserMetadataPatch<A, U> {
+  fn clone(&self) -> Self {
+    Self {
+      client: self.client.clone(),
+
+      id: self.id.clone(),
+      app_metadata: self.app_metadata.clone(),
+      user_metadata: self.user_metadata.clone(),
+      retries: self.retries,
+      options: self.options.clone(),
+      metadata: PhantomData,
+    }
+  }
+}
+
+impl<A, U> AsMut<RequestOptions> for UserMetadataPatch<A, U> {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl<A, U> IntoFuture for UserMetadataPatch<A, U>
+where
+  A: DeserializeOwned + Send + Sync + 'static,
+  U: DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<User<A, U>>;
+  type IntoFuture = SendFuture<User<A, U>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send::<A, U>().await })
+  }
+}
+
+impl<A, U> IntoFuture for &mut UserMetadataPatch<A, U>
+where
+  A: DeserializeOwned + Send + Sync + 'static,
+  U: DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<User<A, U>>;
+  type IntoFuture = SendFuture<User<A, U>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use crate::MetadataPatch;
+
+  #[test]
+  fn test_compile_first_level() {
+    let patch = MetadataPatch::new()
+      .set("plan", "pro")
+      .remove("trial")
+      .clone();
+
+    assert_eq!(patch.compile(None), json!({ "plan": "pro", "trial": null }));
+  }
+
+  #[test]
+  fn test_compile_nested() {
+    let current = json!({
+      "prefs": { "theme": "light", "lang": "en", "beta": true },
+      "plan": "free"
+    });
+    let patch = MetadataPatch::new()
+      .set_path(&["prefs", "theme"], "dark")
+      .remove_path(&["prefs", "beta"])
+      .set_path(&["limits", "seats"], 5)
+      .clone();
+
+    assert_eq!(
+      patch.compile(Some(&current)),
+      json!({
+        "prefs": { "theme": "dark", "lang": "en" },
+        "limits": { "seats": 5 }
+      })
+    );
+  }
+
+  #[test]
+  fn test_compile_remove_missing() {
+    let current = json!({ "prefs": "light", "plan": "free" });
+    let patch = MetadataPatch::new()
+      .remove_path(&["prefs", "beta"])
+      .remove_path(&["limits", "seats"])
+      .set_path(&[], "ignored")
+      .clone();
+
+    assert_eq!(patch.compile(Some(&current)), json!({}));
+    assert_eq!(patch.compile(None), json!({}));
+    assert!(patch.is_applied(Some(&current)));
+  }
+
+  #[test]
+  fn test_is_applied() {
+    let patch = MetadataPatch::new()
+      .set_path(&["prefs", "theme"], "dark")
+      .remove("trial")
+      .clone();
+
+    assert!(patch.is_applied(Some(&json!({ "prefs": { "theme": "dark" } }))));
+    assert!(!patch.is_applied(Some(&json!({ "prefs": { "theme": "light" } }))));
+    assert!(!patch.is_applied(Some(&json!({
+      "prefs": { "theme": "dark" },
+      "trial": true
+    }))));
+    assert!(!patch.is_applied(None));
+  }
+
+  #[test]
+  fn test_is_written() {
+    let snapshot = json!({ "prefs": { "theme": "light", "lang": "en" }, "plan": "free" });
+    let patch = MetadataPatch::new()
+      .set_path(&["prefs", "theme"], "dark")
+      .remove("trial")
+      .clone();
+
+    assert!(patch.is_written(
+      Some(&snapshot),
+      Some(&json!({ "prefs": { "theme": "dark", "lang": "en" }, "plan": "pro" }))
+    ));
+    assert!(!patch.is_written(
+      Some(&snapshot),
+      Some(&json!({ "prefs": { "theme": "dark" }, "plan": "free" }))
+    ));
+    assert!(!patch.is_written(
+      Some(&snapshot),
+      Some(&json!({ "prefs": { "theme": "dark", "lang": "en" }, "trial": true }))
+    ));
+  }
+}
@@ -1,19 +1,47 @@
 //! Update a user.
-use reqwest::Method;
+use std::future::IntoFuture;
+use std::sync::Arc;
+
+use http::Method;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::users::User;
-use crate::{Auth0Client, Auth0Result};
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Auth0Result, ClientId, Endpoint, HttpRequest,
+  RequestOptions, UserId,
+};
+
+const ENDPOINT: Endpoint = Endpoint::new(
+  "api/v2/users/{id}",
+  &["update:users", "update:users_app_metadata"],
+);
 
 /// Update a user.
-#[derive(Serialize)]
-pub struct UserUpdate<'a, A, U> {
+#[derive(Serialize, Clone)]
+pub struct UserUpdate<A = Value, U = Value> {
   #[serde(skip_serializing)]
-  client: &'a Auth0Client,
+  client: Arc<Auth0Client>,
 
   #[serde(skip_serializing)]
-  user_id: String,
+  user_id: UserId,
+  #[serde(flatten)]
+  fields: UserUpdateFields,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  app_metadata: Option<A>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  user_metadata: Option<U>,
+
+  #[serde(skip)]
+  options: RequestOptions,
+}
+
+/// Attributes of a [UserUpdate] request other than the metadata.
+#[derive(Serialize, Clone, Debug, Default)]
+struct UserUpdateFields {
   #[serde(skip_serializing_if = "Option::is_none")]
   blocked: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -39,205 +67,174 @@ pub struct UserUpdate<'a, A, U> {
   #[serde(skip_serializing_if = "Option::is_none")]
   connection: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
-  client_id: Option<String>,
+  client_id: Option<ClientId>,
   #[serde(skip_serializing_if = "Option::is_none")]
   verify_email: Option<bool>,
   #[serde(skip_serializing_if = "Option::is_none")]
   verify_phone_number: Option<bool>,
-
-  #[serde(skip_serializing_if = "Option::is_none")]
-  app_metadata: Option<A>,
-  #[serde(skip_serializing_if = "Option::is_none")]
-  user_metadata: Option<U>,
 }
 
-impl<'a> UserUpdate<'a, (), ()> {
+impl UserUpdate {
   /// Create update user request.
-  pub fn new<S: AsRef<str>>(client: &'a Auth0Client, id: S) -> Self {
+  pub fn new<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
+    Self::typed(client, id)
+  }
+}
+
+impl<A, U> UserUpdate<A, U> {
+  /// Create update user request with `A` and `U` metadata.
+  pub(crate) fn typed<I: Into<UserId>>(client: Arc<Auth0Client>, id: I) -> Self {
     Self {
       client,
 
-      user_id: id.as_ref().to_string(),
-      blocked: None,
-      email: None,
-      email_verified: None,
-      phone_number: None,
-      phone_verified: None,
-      given_name: None,
-      family_name: None,
-      name: None,
-      nickname: None,
-      picture: None,
-      verify_email: None,
-      verify_phone_number: None,
-      password: None,
-      connection: None,
-      client_id: None,
+      user_id: id.into(),
+      fields: Default::default(),
       app_metadata: None,
       user_metadata: None,
+      options: Default::default(),
     }
   }
-}
 
-impl<'a, A: Clone, U: Clone> UserUpdate<'a, A, U> {
   /// ID of the user which can be used when interacting with other APIs.
-  pub fn user_id(&mut self, id: &str) -> &mut Self {
-    self.user_id = id.to_owned();
+  pub fn user_id<I: Into<UserId>>(&mut self, id: I) -> &mut Self {
+    self.user_id = id.into();
     self
   }
 
   /// Whether this user was blocked by an administrator (true) or not (false).
   pub fn blocked(&mut self, blocked: bool) -> &mut Self {
-    self.blocked = Some(blocked);
+    self.fields.blocked = Some(blocked);
     self
   }
 
   /// Email address of this user.
   pub fn email(&mut self, email: &str) -> &mut Self {
-    self.email = Some(email.to_owned());
+    self.fields.email = Some(email.to_owned());
     self
   }
 
   /// Whether this email address is verified (true) or unverified (false). If set to false the user
   /// will not receive a verification email unless `verify_email` is set to true.
   pub fn email_verified(&mut self, email_verified: bool) -> &mut Self {
-    self.email_verified = Some(email_verified);
+    self.fields.email_verified = Some(email_verified);
     self
   }
 
   /// The user's phone number (following the E.164 recommendation), only valid for users from SMS
   /// connections.
   pub fn phone_number(&mut self, phone_number: &str) -> &mut Self {
-    self.phone_number = Some(phone_number.to_owned());
+    self.fields.phone_number = Some(phone_number.to_owned());
     self
   }
 
   /// Whether this phone number has been verified (true) or not (false).
   pub fn phone_verified(&mut self, phone_verified: bool) -> &mut Self {
-    self.phone_verified = Some(phone_verified);
+    self.fields.phone_verified = Some(phone_verified);
     self
   }
 
   /// Given name/first name/forename of this user.
   pub fn given_name(&mut self, given_name: &str) -> &mut Self {
-    self.given_name = Some(given_name.to_owned());
+    self.fields.given_name = Some(given_name.to_owned());
     self
   }
 
   /// Family name/last name/surname of this user.
   pub fn family_name(&mut self, family_name: &str) -> &mut Self {
-    self.family_name = Some(family_name.to_owned());
+    self.fields.family_name = Some(family_name.to_owned());
     self
   }
 
   /// Name of this user.
   pub fn name(&mut self, name: &str) -> &mut Self {
-    self.name = Some(name.to_owned());
+    self.fields.name = Some(name.to_owned());
     self
   }
 
   /// Preferred nickname or alias of this user.
   pub fn nickname(&mut self, nickname: &str) -> &mut Self {
-    self.nickname = Some(nickname.to_owned());
+    self.fields.nickname = Some(nickname.to_owned());
     self
   }
 
   /// URL to picture, photo, or avatar of this user.
   pub fn picture(&mut self, picture: &str) -> &mut Self {
-    self.picture = Some(picture.to_owned());
+    self.fields.picture = Some(picture.to_owned());
     self
   }
 
   /// Whether this user will receive a verification email after creation (true) or no email (false).
   /// Overrides behavior of `email_verified` parameter.
   pub fn verify_email(&mut self, verify_email: bool) -> &mut Self {
-    self.verify_email = Some(verify_email);
+    self.fields.verify_email = Some(verify_email);
     self
   }
 
   /// Whether this user will receive a text after changing the phone number (true) or no text
   /// (false). Only valid when changing phone number.
   pub fn verify_phone_number(&mut self, verify_phone_number: bool) -> &mut Self {
-    self.verify_phone_number = Some(verify_phone_number);
+    self.fields.verify_phone_number = Some(verify_phone_number);
     self
   }
 
   /// New password for this user (mandatory for non-SMS connections).
   pub fn password(&mut self, password: &str) -> &mut Self {
-    self.password = Some(password.to_owned());
+    self.fields.password = Some(password.to_owned());
     self
   }
 
   /// ID of the connection this user should be created in.
   pub fn connection(&mut self, connection: &str) -> &mut Self {
-    self.connection = Some(connection.to_owned());
+    self.fields.connection = Some(connection.to_owned());
     self
   }
 
   /// Auth0 client ID. Only valid when updating email address.
-  pub fn client_id(&mut self, client_id: &str) -> &mut Self {
-    self.client_id = Some(client_id.to_owned());
+  pub fn client_id<I: Into<ClientId>>(&mut self, client_id: I) -> &mut Self {
+    self.fields.client_id = Some(client_id.into());
     self
   }
 
   /// User metadata to which this user has read-only access.
-  pub fn app_metadata<AppMetadata>(
-    &self,
-    app_metadata: AppMetadata,
-  ) -> UserUpdate<'a, AppMetadata, U> {
+  pub fn app_metadata(&mut self, app_metadata: A) -> &mut Self {
+    self.app_metadata = Some(app_metadata);
+    self
+  }
+
+  /// User metadata to which this user has read/write access.
+  pub fn user_metadata(&mut self, user_metadata: U) -> &mut Self {
+    self.user_metadata = Some(user_metadata);
+    self
+  }
+
+  /// Set the `app_metadata` to a value of another type, e.g. a typed struct instead of
+  /// [Value].
+  pub fn with_app_metadata<T>(self, app_metadata: T) -> UserUpdate<T, U> {
     UserUpdate {
       client: self.client,
-      user_id: self.user_id.clone(),
-      blocked: self.blocked,
-      email: self.email.clone(),
-      email_verified: self.email_verified,
-      phone_number: self.phone_number.clone(),
-      phone_verified: self.phone_verified,
-      given_name: self.given_name.clone(),
-      family_name: self.family_name.clone(),
-      name: self.name.clone(),
-      nickname: self.nickname.clone(),
-      picture: self.picture.clone(),
-      password: self.password.clone(),
-      connection: self.connection.clone(),
-      client_id: self.client_id.clone(),
-      verify_email: self.verify_email,
-      verify_phone_number: self.verify_phone_number,
+      user_id: self.user_id,
+      fields: self.fields,
       app_metadata: Some(app_metadata),
-      user_metadata: self.user_metadata.clone(),
+      user_metadata: self.user_metadata,
+      options: self.options,
     }
   }
 
-  /// User metadata to which this user has read/write access.
-  pub fn user_metadata<UserMetadata>(
-    &mut self,
-    user_metadata: UserMetadata,
-  ) -> UserUpdate<'a, A, UserMetadata> {
+  /// Set the `user_metadata` to a value of another type, e.g. a typed struct instead of
+  /// [Value].
+  pub fn with_user_metadata<T>(self, user_metadata: T) -> UserUpdate<A, T> {
     UserUpdate {
       client: self.client,
-      user_id: self.user_id.clone(),
-      blocked: self.blocked,
-      email: self.email.clone(),
-      email_verified: self.email_verified,
-      phone_number: self.phone_number.clone(),
-      phone_verified: self.phone_verified,
-      given_name: self.given_name.clone(),
-      family_name: self.family_name.clone(),
-      name: self.name.clone(),
-      nickname: self.nickname.clone(),
-      picture: self.picture.clone(),
-      password: self.password.clone(),
-      connection: self.connection.clone(),
-      client_id: self.client_id.clone(),
-      verify_email: self.verify_email,
-      verify_phone_number: self.verify_phone_number,
-      app_metadata: self.app_metadata.clone(),
+      user_id: self.user_id,
+      fields: self.fields,
+      app_metadata: self.app_metadata,
       user_metadata: Some(user_metadata),
+      options: self.options,
     }
   }
 }
 
-impl<'a, AIn, UIn> UserUpdate<'a, AIn, UIn> {
+impl<AIn, UIn> UserUpdate<AIn, UIn> {
   /// Send
   pub async fn send<AOut, UOut>(&self) -> Auth0Result<User<AOut, UOut>>
   where
@@ -248,12 +245,52 @@ impl<'a, AIn, UIn> UserUpdate<'a, AIn, UIn> {
   {
     self
       .client
-      .send(
-        self
-          .client
-          .begin(Method::PATCH, &format!("api/v2/users/{}", self.user_id))
-          .json(self),
-      )
+      .send_endpoint(self.build(&self.client), &ENDPOINT)
       .await
   }
 }
+
+impl<A: Serialize, U: Serialize> Auth0RequestBuilder for UserUpdate<A, U> {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin_endpoint(Method::PATCH, &ENDPOINT, &[self.user_id.as_str()]))
+      .json(self)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl<A, U> AsMut<RequestOptions> for UserUpdate<A, U> {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl<A, U> IntoFuture for UserUpdate<A, U>
+where
+  A: Serialize + DeserializeOwned + Send + Sync + 'static,
+  U: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<User<A, U>>;
+  type IntoFuture = SendFuture<User<A, U>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send::<A, U>().await })
+  }
+}
+
+impl<A, U> IntoFuture for &mut UserUpdate<A, U>
+where
+  A: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+  U: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+{
+  type Output = Auth0Result<User<A, U>>;
+  type IntoFuture = SendFuture<User<A, U>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -1,48 +1,143 @@
 //! Retrieve details of users.
-use reqwest::Method;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use http::Method;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
+
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Auth0Result, Endpoint, HttpRequest, RequestOptions,
+  User,
+};
+use crate::{Page, Sort, SortField};
 
-use crate::{Auth0Client, Auth0Result, User};
-use crate::{Page, Sort};
+const ENDPOINT: Endpoint = Endpoint::new("api/v2/users", &["read:users"]);
 
-/// Retrieve details of users.
+/// Fields users can be sorted by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UserSortField {
+  /// `user_id`
+  UserId,
+  /// `email`
+  Email,
+  /// `email_verified`
+  EmailVerified,
+  /// `phone_number`
+  PhoneNumber,
+  /// `username`
+  Username,
+  /// `name`
+  Name,
+  /// `nickname`
+  Nickname,
+  /// `given_name`
+  GivenName,
+  /// `family_name`
+  FamilyName,
+  /// `blocked`
+  Blocked,
+  /// `created_at`
+  CreatedAt,
+  /// `updated_at`
+  UpdatedAt,
+  /// `last_login`
+  LastLogin,
+  /// `last_ip`
+  LastIp,
+  /// `logins_count`
+  LoginsCount,
+}
+
+impl SortField for UserSortField {
+  fn name(&self) -> &'static str {
+    match self {
+      Self::UserId => "user_id",
+      Self::Email => "email",
+      Self::EmailVerified => "email_verified",
+      Self::PhoneNumber => "phone_number",
+      Self::Username => "username",
+      Self::Name => "name",
+      Self::Nickname => "nickname",
+      Self::GivenName => "given_name",
+      Self::FamilyName => "family_name",
+      Self::Blocked => "blocked",
+      Self::CreatedAt => "created_at",
+      Self::UpdatedAt => "updated_at",
+      Self::LastLogin => "last_login",
+      Self::LastIp => "last_ip",
+      Self::LoginsCount => "logins_count",
+    }
+  }
+}
+
+/// Retrieve details of users, returned with `A` and `U` metadata when awaited.
 #[derive(Serialize)]
-pub struct UsersFind<'a> {
+pub struct UsersFind<A = Value, U = Value> {
   #[serde(skip_serializing)]
-  client: &'a Auth0Client,
+  client: Arc<Auth0Client>,
 
   #[serde(flatten)]
   page: Page,
   #[serde(skip_serializing_if = "Sort::is_emtpy")]
-  sort: Sort,
+  sort: Sort<UserSortField>,
+
+  #[serde(skip)]
+  options: RequestOptions,
+  #[serde(skip)]
+  metadata: PhantomData<fn() -> (A, U)>,
 }
 
-impl<'a> UsersFind<'a> {
+impl UsersFind {
   /// Create find users request.
-  pub fn new(client: &'a Auth0Client) -> Self {
+  pub fn new(client: Arc<Auth0Client>) -> Self {
+    Self::typed(client)
+  }
+}
+
+impl<A, U> UsersFind<A, U> {
+  /// Create find users request with `A` and `U` metadata.
+  pub(crate) fn typed(client: Arc<Auth0Client>) -> Self {
     Self {
       client,
 
       page: Default::default(),
       sort: Default::default(),
+      options: Default::default(),
+      metadata: PhantomData,
+    }
+  }
+}
+
+impl<A, U> Clone for UsersFind<A, U> {
+  fn clone(&self) -> Self {
+    Self {
+      client: self.client.clone(),
+
+      page: self.page.clone(),
+      sort: self.sort.clone(),
+      options: self.options.clone(),
+      metadata: PhantomData,
     }
   }
 }
 
-impl<'a> AsMut<Page> for UsersFind<'a> {
+impl<A, U> AsMut<Page> for UsersFind<A, U> {
   fn as_mut(&mut self) -> &mut Page {
     &mut self.page
   }
 }
 
-impl<'a> AsMut<Sort> for UsersFind<'a> {
-  fn as_mut(&mut self) -> &mut Sort {
+impl<A, U> AsMut<Sort<UserSortField>> for UsersFind<A, U> {
+  fn as_mut(&mut self) -> &mut Sort<UserSortField> {
     &mut self.sort
   }
 }
 
-impl<'a> UsersFind<'a> {
+impl<A, U> UsersFind<A, U> {
   /// Send
   pub async fn send<AOut, UOut>(&self) -> Auth0Result<Vec<User<AOut, UOut>>>
   where
@@ -51,7 +146,52 @@ impl<'a> UsersFind<'a> {
   {
     self
       .client
-      .send(self.client.begin(Method::GET, "api/v2/users").query(self))
+      .send_endpoint(self.build(&self.client), &ENDPOINT)
       .await
   }
 }
+
+impl<A, U> Auth0RequestBuilder for UsersFind<A, U> {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::GET, ENDPOINT.path))
+      .query(self)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl<A, U> AsMut<RequestOptions> for UsersFind<A, U> {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl<A, U> IntoFuture for UsersFind<A, U>
+where
+  A: DeserializeOwned + Send + Sync + 'static,
+  U: DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<Vec<User<A, U>>>;
+  type IntoFuture = SendFuture<Vec<User<A, U>>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send::<A, U>().await })
+  }
+}
+
+impl<A, U> IntoFuture for &mut UsersFind<A, U>
+where
+  A: DeserializeOwned + Send + Sync + 'static,
+  U: DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<Vec<User<A, U>>>;
+  type IntoFuture = SendFuture<Vec<User<A, U>>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
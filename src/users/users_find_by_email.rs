@@ -0,0 +1,189 @@
+//! Find users by email.
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use http::Method;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::SendFuture;
+use crate::{
+  Auth0Client, Auth0RequestBuilder, Auth0Result, Endpoint, HttpRequest, RequestOptions,
+  User,
+};
+
+pub(crate) const ENDPOINT: Endpoint =
+  Endpoint::new("api/v2/users-by-email", &["read:users"]);
+
+/// Find users by email, returned with `A` and `U` metadata when awaited.
+#[derive(Serialize)]
+pub struct UsersFindByEmail<A = Value, U = Value> {
+  #[serde(skip_serializing)]
+  client: Arc<Auth0Client>,
+
+  #[serde(skip)]
+  email: String,
+  #[serde(skip)]
+  case_insensitive: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  fields: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  include_fields: Option<bool>,
+
+  #[serde(skip)]
+  options: RequestOptions,
+  #[serde(skip)]
+  metadata: PhantomData<fn() -> (A, U)>,
+}
+
+impl UsersFindByEmail {
+  /// Create find users by email request.
+  ///
+  /// # Arguments
+  /// * `email` - The email address of the users to retrieve.
+  pub fn new<S: AsRef<str>>(client: Arc<Auth0Client>, email: S) -> Self {
+    Self::typed(client, email)
+  }
+}
+
+impl<A, U> UsersFindByEmail<A, U> {
+  /// Create find users by email request with `A` and `U` metadata.
+  pub(crate) fn typed<S: AsRef<str>>(client: Arc<Auth0Client>, email: S) -> Self {
+    Self {
+      client,
+
+      email: email.as_ref().to_owned(),
+      case_insensitive: false,
+      fields: None,
+      include_fields: None,
+      options: Default::default(),
+      metadata: PhantomData,
+    }
+  }
+
+  /// Fields to include or exclude (based on value provided for `include_fields`) in the
+  /// result.  Leave empty to retrieve all fields.
+  pub fn fields<S: AsRef<str>>(&mut self, fields: &[S]) -> &mut Self {
+    let fields = fields
+      .iter()
+      .map(|field| field.as_ref())
+      .collect::<Vec<_>>()
+      .join(",");
+
+    self.fields = Some(fields);
+    self
+  }
+
+  /// Whether specified fields are to be included (true) or excluded (false).  Defaults to
+  /// true.
+  pub fn include_fields(&mut self, include_fields: bool) -> &mut Self {
+    self.include_fields = Some(include_fields);
+    self
+  }
+
+  /// Whether users saved with the email in lower case are found too.  Defaults to false.
+  ///
+  /// The API matches emails case-sensitively, so the email is searched for as given and,
+  /// when it differs, in lower case like Auth0 saves the email of users it is the identity
+  /// provider of.  The users found are merged, users of other identity providers saved with
+  /// another casing aren't found.
+  pub fn case_insensitive(&mut self, case_insensitive: bool) -> &mut Self {
+    self.case_insensitive = case_insensitive;
+    self
+  }
+
+  /// Send
+  pub async fn send<AOut, UOut>(&self) -> Auth0Result<Vec<User<AOut, UOut>>>
+  where
+    AOut: DeserializeOwned,
+    UOut: DeserializeOwned,
+  {
+    let mut users: Vec<User<AOut, UOut>> = self
+      .client
+      .send_endpoint(self.build(&self.client), &ENDPOINT)
+      .await?;
+
+    let lowercase = self.email.to_lowercase();
+    if !self.case_insensitive || lowercase == self.email {
+      return Ok(users);
+    }
+
+    let req = self.request(&self.client, &lowercase);
+    let found: Vec<User<AOut, UOut>> = self.client.send_endpoint(req, &ENDPOINT).await?;
+    for user in found {
+      if !users.iter().any(|other| other.user_id == user.user_id) {
+        users.push(user);
+      }
+    }
+
+    Ok(users)
+  }
+
+  /// Create request finding users by `email`.
+  fn request(&self, client: &Auth0Client, email: &str) -> HttpRequest {
+    self
+      .options
+      .apply(client.begin(Method::GET, ENDPOINT.path))
+      .query(&[("email", email)])
+      .query(self)
+  }
+}
+
+impl<A, U> Clone for UsersFindByEmail<A, U> {
+  fn clone(&self) -> Self {
+    Self {
+      client: self.client.clone(),
+
+      email: self.email.clone(),
+      case_insensitive: self.case_insensitive,
+      fields: self.fields.clone(),
+      include_fields: self.include_fields,
+      options: self.options.clone(),
+      metadata: PhantomData,
+    }
+  }
+}
+
+impl<A, U> Auth0RequestBuilder for UsersFindByEmail<A, U> {
+  fn build(&self, client: &Auth0Client) -> HttpRequest {
+    self.request(client, &self.email)
+  }
+
+  fn endpoint(&self) -> &'static Endpoint {
+    &ENDPOINT
+  }
+}
+
+impl<A, U> AsMut<RequestOptions> for UsersFindByEmail<A, U> {
+  fn as_mut(&mut self) -> &mut RequestOptions {
+    &mut self.options
+  }
+}
+
+impl<A, U> IntoFuture for UsersFindByEmail<A, U>
+where
+  A: DeserializeOwned + Send + Sync + 'static,
+  U: DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<Vec<User<A, U>>>;
+  type IntoFuture = SendFuture<Vec<User<A, U>>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    Box::pin(async move { self.send::<A, U>().await })
+  }
+}
+
+impl<A, U> IntoFuture for &mut UsersFindByEmail<A, U>
+where
+  A: DeserializeOwned + Send + Sync + 'static,
+  U: DeserializeOwned + Send + Sync + 'static,
+{
+  type Output = Auth0Result<Vec<User<A, U>>>;
+  type IntoFuture = SendFuture<Vec<User<A, U>>>;
+
+  fn into_future(self) -> Self::IntoFuture {
+    self.clone().into_future()
+  }
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "blocking")]
+use std::sync::Arc;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use auth0_management::{Pageable, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    let body = if req.path.starts_with("/api/v2/users?") {
+      json!([{ "user_id": "auth0|1" }])
+    } else if req.path.starts_with("/api/v2/user-blocks/") {
+      json!({ "blocked_for": [{ "identifier": "test@example.test", "ip": null }] })
+    } else {
+      json!({ "user_id": "auth0|1", "email": "test@example.test" })
+    };
+
+    MockResponse::json(200, body).with_rate_limit()
+  })
+}
+
+#[test]
+fn test_blocking_requests() {
+  let server = tenant_server();
+  let auth0 = server.builder().build_blocking().unwrap();
+
+  let users = auth0.users.find().per_page(5).send().unwrap();
+  assert_eq!(users[0].user_id, "auth0|1");
+
+  let mut update = auth0.users.update(UserId::new("1"));
+  update.email("test@example.test");
+  let user = update.send().unwrap();
+  assert_eq!(user.email.as_deref(), Some("test@example.test"));
+
+  let blocks = auth0.user_blocks.get(UserId::new("1")).unwrap();
+  assert_eq!(blocks[0].identifier, "test@example.test");
+
+  assert_eq!(server.requests_to("/oauth/token").len(), 1);
+  assert_eq!(server.requests_to("/api/v2/users?per_page=5").len(), 1);
+
+  let update = &server.requests_to("/api/v2/users/1")[0];
+  assert_eq!(update.method, "PATCH");
+  assert_eq!(
+    serde_json::from_str::<Value>(&update.body).unwrap(),
+    json!({ "email": "test@example.test" })
+  );
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Plan {
+  plan: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Profile {
+  theme: String,
+}
+
+#[test]
+fn test_blocking_metadata_type() {
+  let server = tenant_server();
+  let auth0 = server.builder().build_blocking().unwrap();
+
+  let create = auth0.users.create().map(|create| {
+    create.with_app_metadata(Plan {
+      plan: "free".to_owned(),
+    })
+  });
+  let user = create.send().unwrap();
+  assert_eq!(user.app_metadata(), None);
+
+  let body = &server.requests_to("/api/v2/users")[0].body;
+  assert_eq!(
+    serde_json::from_str::<Value>(body).unwrap()["app_metadata"],
+    json!({ "plan": "free" })
+  );
+}
+
+#[test]
+fn test_blocking_with_metadata() {
+  let server = tenant_server();
+  let auth0 = server
+    .builder()
+    .build_blocking()
+    .unwrap()
+    .with_metadata::<Value, Profile>();
+
+  let mut update = auth0.users.update(UserId::new("1"));
+  update.user_metadata(Profile {
+    theme: "dark".to_owned(),
+  });
+  let user = update.send().unwrap();
+  assert_eq!(user.user_metadata(), None);
+
+  let user = auth0.users.get(UserId::new("1")).unwrap();
+  assert_eq!(user.user_metadata(), None);
+
+  let update = &server.requests_to("/api/v2/users/1")[0];
+  assert_eq!(
+    serde_json::from_str::<Value>(&update.body).unwrap(),
+    json!({ "user_metadata": { "theme": "dark" } })
+  );
+}
+
+#[test]
+fn test_blocking_from_threads() {
+  let server = tenant_server();
+  let auth0 = Arc::new(server.builder().build_blocking().unwrap());
+
+  let threads = (0..4)
+    .map(|_| {
+      let auth0 = auth0.clone();
+      thread::spawn(move || auth0.users.get(UserId::new("1")).unwrap())
+    })
+    .collect::<Vec<_>>();
+
+  for thread in threads {
+    assert_eq!(thread.join().unwrap().user_id, "auth0|1");
+  }
+}
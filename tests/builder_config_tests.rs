@@ -0,0 +1,151 @@
+use std::fs;
+
+use serde_json::{json, Value};
+
+use auth0_management::{Auth0Builder, Auth0BuilderError, User, UserId};
+
+use crate::mock::{MockRequest, MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|_| {
+    MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+  })
+}
+
+async fn token_request(server: &MockServer, builder: Auth0Builder) -> MockRequest {
+  let auth0 = builder.build().unwrap();
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  server.requests_to("/oauth/token").remove(0)
+}
+
+#[tokio::test]
+async fn test_from_config_toml() {
+  let production = tenant_server();
+  let staging = tenant_server();
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("auth0.toml");
+  fs::write(
+    &path,
+    format!(
+      r#"
+[production]
+domain = "{}"
+client_id = "PRODUCTION_ID"
+client_secret = "PRODUCTION_SECRET"
+
+[staging]
+domain = "{}"
+audience = "https://staging.example.test/api/v2/"
+client_id = "STAGING_ID"
+client_secret = "STAGING_SECRET"
+"#,
+      production.domain(),
+      staging.domain()
+    ),
+  )
+  .unwrap();
+
+  let builder = Auth0Builder::from_config(&path, "staging").unwrap();
+  let req = token_request(&staging, builder).await;
+  assert_eq!(req.form("client_id").as_deref(), Some("STAGING_ID"));
+  assert_eq!(
+    req.form("audience").as_deref(),
+    Some("https://staging.example.test/api/v2/")
+  );
+
+  // Audience defaults to the management API of the domain.
+  let builder = Auth0Builder::from_config(&path, "production").unwrap();
+  let req = token_request(&production, builder).await;
+  assert_eq!(req.form("client_id").as_deref(), Some("PRODUCTION_ID"));
+  assert_eq!(
+    req.form("audience"),
+    Some(format!("{}/api/v2/", production.domain()))
+  );
+}
+
+#[tokio::test]
+async fn test_from_config_json() {
+  let server = tenant_server();
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("auth0.json");
+  fs::write(
+    &path,
+    json!({
+      "default": {
+        "domain": server.domain(),
+        "client_id": "CLIENT_ID",
+        "client_secret": "CLIENT_SECRET"
+      }
+    })
+    .to_string(),
+  )
+  .unwrap();
+
+  let builder = Auth0Builder::from_config(&path, "default").unwrap();
+  let req = token_request(&server, builder).await;
+  assert_eq!(req.form("client_secret").as_deref(), Some("CLIENT_SECRET"));
+}
+
+#[test]
+fn test_from_config_errors() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("auth0.toml");
+  fs::write(&path, "[production]\ndomain = \"example.auth0.com\"\n").unwrap();
+
+  assert_eq!(
+    Auth0Builder::from_config(&path, "staging").err(),
+    Some(Auth0BuilderError::MissingProfile("staging".to_owned()))
+  );
+  assert!(matches!(
+    Auth0Builder::from_config(dir.path().join("missing.toml"), "production"),
+    Err(Auth0BuilderError::Config(_))
+  ));
+
+  fs::write(&path, "[production]\ndomian = \"example.auth0.com\"\n").unwrap();
+  assert!(matches!(
+    Auth0Builder::from_config(&path, "production"),
+    Err(Auth0BuilderError::Config(_))
+  ));
+
+  // Private keys are useless without the id of the registered public key.
+  fs::write(
+    &path,
+    "[production]\ndomain = \"example.auth0.com\"\nprivate_key = \"PEM\"\n",
+  )
+  .unwrap();
+  assert_eq!(
+    Auth0Builder::from_config(&path, "production").err(),
+    Some(Auth0BuilderError::MissingKeyId)
+  );
+  assert_eq!(
+    Auth0Builder::new()
+      .domain("example.auth0.com")
+      .client_id("CLIENT_ID")
+      .private_key("PEM", "")
+      .build()
+      .err(),
+    Some(Auth0BuilderError::MissingKeyId)
+  );
+}
+
+#[tokio::test]
+async fn test_from_env_prefixed() {
+  let server = tenant_server();
+  std::env::set_var("BUILDER_TEST_DOMAIN", server.domain());
+  std::env::set_var("BUILDER_TEST_CLIENT_ID", "ENV_ID");
+  std::env::set_var("BUILDER_TEST_CLIENT_SECRET", "ENV_SECRET");
+
+  let builder = Auth0Builder::from_env_prefixed("BUILDER_TEST_").unwrap();
+  let req = token_request(&server, builder).await;
+  assert_eq!(req.form("client_id").as_deref(), Some("ENV_ID"));
+  assert_eq!(req.form("client_secret").as_deref(), Some("ENV_SECRET"));
+
+  std::env::set_var("BUILDER_TEST_SIGNING_ALGORITHM", "HS256");
+  assert!(matches!(
+    Auth0Builder::from_env_prefixed("BUILDER_TEST_"),
+    Err(Auth0BuilderError::Config(_))
+  ));
+}
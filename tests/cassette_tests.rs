@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use auth0_management::{Auth0, Auth0Error, CassetteMode, Pageable, User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    let body = if req.path.starts_with("/api/v2/users?") {
+      json!([{ "user_id": "auth0|1" }])
+    } else {
+      json!({ "user_id": "auth0|1", "email": "test@example.test" })
+    };
+
+    MockResponse::json(200, body).with_rate_limit()
+  })
+}
+
+fn auth0(domain: &str, cassette: &Path, mode: CassetteMode) -> Auth0 {
+  Auth0::builder()
+    .domain(domain)
+    .audience("https://example.auth0.com/api/v2/")
+    .client_id("CLIENT_ID")
+    .client_secret("CLIENT_SECRET")
+    .cassette(cassette, mode)
+    .build()
+    .unwrap()
+}
+
+async fn run(auth0: &Auth0) {
+  let users: Vec<User<Value, Value>> =
+    auth0.users.find().page(1).per_page(2).send().await.unwrap();
+  assert_eq!(users[0].user_id, "auth0|1");
+
+  let user: User<Value, Value> = auth0
+    .users
+    .update(UserId::new("1"))
+    .email("test@example.test")
+    .password("SECRET_PASSWORD")
+    .send()
+    .await
+    .unwrap();
+  assert_eq!(user.email.as_deref(), Some("test@example.test"));
+}
+
+#[tokio::test]
+async fn test_record_and_replay() {
+  let dir = tempfile::tempdir().unwrap();
+  let cassette = dir.path().join("cassettes/users.json");
+  let server = tenant_server();
+
+  run(&auth0(&server.domain(), &cassette, CassetteMode::Record)).await;
+  assert_eq!(server.requests().len(), 3);
+
+  let recorded = fs::read_to_string(&cassette).unwrap();
+  assert!(recorded.contains("/api/v2/users"));
+  assert!(!recorded.contains("TOKEN"));
+  assert!(!recorded.contains("CLIENT_SECRET"));
+  assert!(!recorded.contains("SECRET_PASSWORD"));
+
+  drop(server);
+  run(&auth0(
+    "http://127.0.0.1:1",
+    &cassette,
+    CassetteMode::Replay,
+  ))
+  .await;
+}
+
+#[tokio::test]
+async fn test_auto_mode() {
+  let dir = tempfile::tempdir().unwrap();
+  let cassette = dir.path().join("users.json");
+  let server = tenant_server();
+
+  run(&auth0(&server.domain(), &cassette, CassetteMode::Auto)).await;
+  run(&auth0(&server.domain(), &cassette, CassetteMode::Auto)).await;
+
+  assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_replay_matches_query_and_body() {
+  let dir = tempfile::tempdir().unwrap();
+  let cassette = dir.path().join("users.json");
+  let server = tenant_server();
+
+  run(&auth0(&server.domain(), &cassette, CassetteMode::Record)).await;
+
+  let auth0 = auth0(&server.domain(), &cassette, CassetteMode::Replay);
+  let other_page = auth0
+    .users
+    .find()
+    .page(2)
+    .per_page(2)
+    .send::<Value, Value>()
+    .await;
+  assert!(matches!(other_page, Err(Auth0Error::Http(_))));
+
+  let other_body = auth0
+    .users
+    .update(UserId::new("1"))
+    .email("other@example.test")
+    .send::<Value, Value>()
+    .await;
+  assert!(matches!(other_body, Err(Auth0Error::Http(_))));
+  assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn test_replay_missing_cassette() {
+  let dir = tempfile::tempdir().unwrap();
+
+  assert!(Auth0::builder()
+    .domain("example.auth0.com")
+    .client_id("CLIENT_ID")
+    .client_secret("CLIENT_SECRET")
+    .cassette(dir.path().join("missing.json"), CassetteMode::Replay)
+    .build()
+    .is_err());
+}
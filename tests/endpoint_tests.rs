@@ -0,0 +1,103 @@
+use serde_json::{json, Value};
+
+use auth0_management::{Auth0Error, Endpoint, EndpointError, User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+const USER: Endpoint = Endpoint::new("api/v2/users/{id}", &[]);
+const USER_ROLE: Endpoint = Endpoint::new("api/v2/users/{id}/roles/{role}", &[]);
+
+#[test]
+fn test_render() {
+  assert_eq!(USER.render(&["1"]).unwrap(), "api/v2/users/1");
+  assert_eq!(
+    USER_ROLE.render(&["1", "rol_abc"]).unwrap(),
+    "api/v2/users/1/roles/rol_abc"
+  );
+  assert_eq!(
+    USER.render(&["A-Za-z0-9-._~"]).unwrap(),
+    "api/v2/users/A-Za-z0-9-._~"
+  );
+}
+
+#[test]
+fn test_render_pipes() {
+  assert_eq!(
+    USER.render(&["auth0|abc"]).unwrap(),
+    "api/v2/users/auth0%7Cabc"
+  );
+  assert_eq!(
+    USER.render(&["google-oauth2|123"]).unwrap(),
+    "api/v2/users/google-oauth2%7C123"
+  );
+}
+
+#[test]
+fn test_render_slashes() {
+  assert_eq!(
+    USER.render(&["../../clients"]).unwrap(),
+    "api/v2/users/..%2F..%2Fclients"
+  );
+  assert_eq!(
+    USER_ROLE.render(&["a/b", "c\\d"]).unwrap(),
+    "api/v2/users/a%2Fb/roles/c%5Cd"
+  );
+}
+
+#[test]
+fn test_render_spaces() {
+  assert_eq!(
+    USER.render(&["john doe"]).unwrap(),
+    "api/v2/users/john%20doe"
+  );
+  assert_eq!(
+    USER.render(&["a+b?c=d#e&f"]).unwrap(),
+    "api/v2/users/a%2Bb%3Fc%3Dd%23e%26f"
+  );
+}
+
+#[test]
+fn test_render_unicode() {
+  assert_eq!(
+    USER.render(&["jürgen"]).unwrap(),
+    "api/v2/users/j%C3%BCrgen"
+  );
+  assert_eq!(
+    USER.render(&["samlp|東京"]).unwrap(),
+    "api/v2/users/samlp%7C%E6%9D%B1%E4%BA%AC"
+  );
+}
+
+#[test]
+fn test_render_rejects_dot_segments() {
+  for arg in ["", ".", ".."] {
+    assert_eq!(
+      USER.render(&[arg]),
+      Err(EndpointError::InvalidPathParameter(arg.to_owned()))
+    );
+  }
+  assert_eq!(
+    USER_ROLE.render(&["1", ".."]),
+    Err(EndpointError::InvalidPathParameter("..".to_owned()))
+  );
+  assert_eq!(USER.render(&["..."]).unwrap(), "api/v2/users/...");
+}
+
+#[tokio::test]
+async fn test_dot_segments_are_not_sent() {
+  let server = MockServer::tenant(|_| MockResponse::json(200, json!({})));
+  let auth0 = server.auth0();
+
+  for id in ["", ".", ".."] {
+    let res: Result<User<Value, Value>, _> = auth0.users.get(UserId::new(id)).await;
+    assert!(matches!(res, Err(Auth0Error::Http(err)) if err.is_builder()));
+    assert!(matches!(
+      auth0.users.delete(UserId::new(id)).await,
+      Err(Auth0Error::Http(err)) if err.is_builder()
+    ));
+  }
+
+  assert!(server.requests().is_empty());
+}
@@ -0,0 +1,126 @@
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+
+use auth0_management::{Permission, Ticket, User, UserBlocks, UserEnrollment, UserLog};
+
+#[test]
+fn test_user() {
+  let user: User<Value, Value> =
+    serde_json::from_str(include_str!("fixtures/user.json")).unwrap();
+
+  assert_eq!(user.user_id, "auth0|5f7c8ec7c33c6c004bbafe82");
+  assert_eq!(user.email.as_deref(), Some("jane.doe@example.test"));
+  assert_eq!(user.email_verified, Some(true));
+  assert_eq!(user.identities.as_ref().unwrap()[0].provider, "auth0");
+  assert_eq!(user.identities.as_ref().unwrap()[0].is_social, Some(false));
+  assert_eq!(user.logins_count, Some(12));
+  assert_eq!(
+    user.created_at,
+    Some(
+      Utc.with_ymd_and_hms(2021, 3, 2, 15, 4, 5).unwrap()
+        + chrono::Duration::milliseconds(123)
+    )
+  );
+  assert_eq!(
+    user.app_metadata(),
+    Some(&json!({ "plan": "pro", "roles": ["admin"] }))
+  );
+  assert_eq!(user.user_metadata(), Some(&json!({ "theme": "dark" })));
+  assert!(user.extra.is_empty());
+
+  let raw: Value = serde_json::from_str(include_str!("fixtures/user.json")).unwrap();
+  assert_eq!(serde_json::to_value(&user).unwrap(), raw);
+}
+
+#[test]
+fn test_user_empty_metadata() {
+  let user: User<Value, Value> =
+    serde_json::from_str(include_str!("fixtures/user_empty_metadata.json")).unwrap();
+
+  assert_eq!(user.identities, Some(vec![]));
+  assert_eq!(user.app_metadata, Some(Some(json!({}))));
+  assert_eq!(user.user_metadata, Some(None));
+  assert_eq!(user.user_metadata(), None);
+
+  let raw: Value =
+    serde_json::from_str(include_str!("fixtures/user_empty_metadata.json")).unwrap();
+  assert_eq!(serde_json::to_value(&user).unwrap(), raw);
+}
+
+#[test]
+fn test_users() {
+  let users: Vec<User<Value, Value>> =
+    serde_json::from_str(include_str!("fixtures/users.json")).unwrap();
+
+  assert_eq!(users.len(), 3);
+  assert_eq!(users[1].user_id, "github|10987654321");
+  assert_eq!(
+    users[1].identities.as_ref().unwrap()[0].user_id,
+    "10987654321"
+  );
+  assert_eq!(users[1].email, None);
+  assert_eq!(users[2].phone_number.as_deref(), Some("+15555550100"));
+  assert_eq!(users[2].phone_verified, Some(true));
+
+  let raw: Value = serde_json::from_str(include_str!("fixtures/users.json")).unwrap();
+  assert_eq!(serde_json::to_value(&users).unwrap(), raw);
+}
+
+#[test]
+fn test_user_logs() {
+  let logs: Vec<UserLog> =
+    serde_json::from_str(include_str!("fixtures/user_logs.json")).unwrap();
+
+  assert_eq!(logs.len(), 1);
+  assert_eq!(logs[0].kind, "s");
+  assert_eq!(logs[0].user_id, "auth0|5f7c8ec7c33c6c004bbafe82");
+  assert!(!logs[0].is_mobile);
+  assert_eq!(logs[0].details["elapsedTime"], 2412);
+  assert_eq!(logs[0].location_info.country_code3, "USA");
+  assert_eq!(logs[0].audience, None);
+}
+
+#[test]
+fn test_user_enrollments() {
+  let enrollments: Vec<UserEnrollment> =
+    serde_json::from_str(include_str!("fixtures/user_enrollments.json")).unwrap();
+
+  assert_eq!(enrollments[0].kind, "sms");
+  assert_eq!(enrollments[0].status, "confirmed");
+  assert_eq!(
+    enrollments[0].enrolled_at,
+    Utc.with_ymd_and_hms(2021, 3, 2, 15, 10, 0).unwrap()
+  );
+}
+
+#[test]
+fn test_user_permissions() {
+  let permissions: Vec<Permission> =
+    serde_json::from_str(include_str!("fixtures/user_permissions.json")).unwrap();
+
+  assert_eq!(permissions[0].name, "read:items");
+  assert_eq!(
+    permissions[0].resource_server_identifier,
+    "https://items.example.test"
+  );
+}
+
+#[test]
+fn test_user_blocks() {
+  let blocks: UserBlocks =
+    serde_json::from_str(include_str!("fixtures/user_blocks.json")).unwrap();
+
+  assert_eq!(blocks.blocked_for.len(), 2);
+  assert_eq!(blocks.blocked_for[0].ip.as_deref(), Some("192.0.2.10"));
+  assert_eq!(blocks.blocked_for[1].ip, None);
+}
+
+#[test]
+fn test_ticket() {
+  let ticket: Ticket =
+    serde_json::from_str(include_str!("fixtures/ticket.json")).unwrap();
+
+  assert!(ticket
+    .ticket
+    .starts_with("https://example.auth0.com/lo/reset?ticket="));
+}
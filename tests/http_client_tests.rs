@@ -0,0 +1,123 @@
+use std::thread;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use auth0_management::{
+  Auth0, Auth0Builder, Auth0Error, Configurable, StaticToken, User, UserId,
+};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+/// Server answering after `delay`.
+fn slow_server(delay: Duration) -> MockServer {
+  MockServer::start(move |_| {
+    thread::sleep(delay);
+
+    MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+  })
+}
+
+fn builder(server: &MockServer) -> Auth0Builder {
+  Auth0::builder()
+    .domain(&server.domain())
+    .token_provider(StaticToken::new("TOKEN"))
+}
+
+fn is_timeout(res: Result<User<Value, Value>, Auth0Error>) -> bool {
+  matches!(res, Err(Auth0Error::Http(err)) if err.is_timeout())
+}
+
+#[tokio::test]
+async fn test_default_user_agent() {
+  let server = slow_server(Duration::from_millis(0));
+  let auth0 = builder(&server).build().unwrap();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  let user_agent = server.requests()[0]
+    .header("user-agent")
+    .unwrap()
+    .to_owned();
+  assert!(user_agent.starts_with("auth0-management/"));
+}
+
+#[tokio::test]
+async fn test_http_options() {
+  let server = slow_server(Duration::from_millis(0));
+  let auth0 = builder(&server)
+    .user_agent("my-service/1.0")
+    .connect_timeout(Duration::from_secs(5))
+    .pool_max_idle_per_host(1)
+    .pool_idle_timeout(Duration::from_secs(30))
+    .build()
+    .unwrap();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  assert_eq!(
+    server.requests()[0].header("user-agent"),
+    Some("my-service/1.0")
+  );
+}
+
+#[tokio::test]
+async fn test_user_supplied_client() {
+  let server = slow_server(Duration::from_millis(0));
+  let client = Client::builder()
+    .user_agent("custom-client")
+    .build()
+    .unwrap();
+  let auth0 = builder(&server)
+    .user_agent("ignored")
+    .http_client(client)
+    .build()
+    .unwrap();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  assert_eq!(
+    server.requests()[0].header("user-agent"),
+    Some("custom-client")
+  );
+}
+
+#[tokio::test]
+async fn test_client_timeout() {
+  let server = slow_server(Duration::from_millis(500));
+  let auth0 = builder(&server)
+    .timeout(Duration::from_millis(100))
+    .build()
+    .unwrap();
+
+  assert!(is_timeout(auth0.users.get(UserId::new("auth0|1")).await));
+}
+
+#[tokio::test]
+async fn test_request_timeout_override() {
+  let server = slow_server(Duration::from_millis(300));
+  let auth0 = builder(&server)
+    .timeout(Duration::from_millis(100))
+    .build()
+    .unwrap();
+
+  let res = auth0
+    .users
+    .update(UserId::new("auth0|1"))
+    .timeout(Duration::from_secs(5))
+    .email("test@example.test")
+    .send::<Value, Value>()
+    .await;
+  assert!(res.is_ok());
+
+  let res = auth0
+    .users
+    .find()
+    .timeout(Duration::from_millis(50))
+    .send::<Value, Value>()
+    .await;
+  assert!(matches!(res, Err(Auth0Error::Http(err)) if err.is_timeout()));
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use auth0_management::{ClientId, ConnectionId, LogId, OrgId, RoleId, User, UserId};
+
+#[test]
+fn test_user_id_parts() {
+  let id: UserId = "google-oauth2|123".parse().unwrap();
+  assert_eq!(id.split(), Some(("google-oauth2", "123")));
+  assert_eq!(id.provider(), Some("google-oauth2"));
+  assert_eq!(id.provider_id(), "123");
+
+  let id = UserId::from_parts("samlp|acme", "jane@example.test");
+  assert_eq!(id, "samlp|acme|jane@example.test");
+  assert_eq!(id.provider(), Some("samlp"));
+  assert_eq!(id.provider_id(), "acme|jane@example.test");
+
+  let id = UserId::new("5f7c8ec7c33c6c004bbafe82");
+  assert_eq!(id.split(), None);
+  assert_eq!(id.provider(), None);
+  assert_eq!(id.provider_id(), "5f7c8ec7c33c6c004bbafe82");
+}
+
+#[test]
+fn test_display_and_conversions() {
+  let id = UserId::new("auth0|1");
+  assert_eq!(id.to_string(), "auth0|1");
+  assert_eq!(id.as_str(), "auth0|1");
+  assert_eq!(UserId::from(&id), id);
+  assert_eq!(UserId::new("auth0|1".to_owned()), id);
+  assert_eq!("auth0|1".parse::<UserId>().unwrap(), id);
+  assert_eq!(String::from(id.clone()), "auth0|1");
+  assert_eq!(id.into_string(), "auth0|1");
+
+  assert_eq!(ClientId::new("CLIENT_ID").as_ref(), "CLIENT_ID");
+  assert_eq!(RoleId::new("rol_1").to_string(), "rol_1");
+  assert_eq!(ConnectionId::new("con_1"), "con_1".to_owned());
+  assert_eq!(format!("{}", OrgId::new("org_1")), "org_1");
+  assert_eq!(LogId::new("900").as_str(), "900");
+}
+
+#[test]
+fn test_serde() {
+  let ids: HashMap<String, UserId> =
+    serde_json::from_value(json!({ "owner": "auth0|1" })).unwrap();
+  assert_eq!(ids["owner"], "auth0|1");
+  assert_eq!(
+    serde_json::to_value(&ids["owner"]).unwrap(),
+    json!("auth0|1")
+  );
+  assert_eq!(
+    serde_json::to_value(OrgId::new("org_1")).unwrap(),
+    json!("org_1")
+  );
+
+  let user: User<(), ()> =
+    serde_json::from_value(json!({ "user_id": "github|10987654321" })).unwrap();
+  assert_eq!(user.user_id.provider(), Some("github"));
+}
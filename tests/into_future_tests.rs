@@ -0,0 +1,163 @@
+use std::future::IntoFuture;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use auth0_management::{
+  Pageable, User, UserCreate, UserId, UserLogsGet, UserUpdate, UsersFind,
+  UsersFindByEmail,
+};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    let body = if req.path.starts_with("/api/v2/users?") || req.path == "/api/v2/users" {
+      if req.method == "POST" {
+        json!({ "user_id": "auth0|new" })
+      } else {
+        json!([{ "user_id": "auth0|1" }, { "user_id": "auth0|2" }])
+      }
+    } else {
+      let id = req.path.trim_start_matches("/api/v2/users/");
+      json!({ "user_id": id.replace("%7C", "|"), "app_metadata": { "plan": "pro" } })
+    };
+
+    MockResponse::json(200, body).with_rate_limit()
+  })
+}
+
+fn assert_send_static<T: Send + 'static>() {}
+
+#[test]
+fn test_builders_are_send_static() {
+  assert_send_static::<UsersFind>();
+  assert_send_static::<UsersFindByEmail>();
+  assert_send_static::<UserCreate<Value, Value>>();
+  assert_send_static::<UserUpdate<Value, Value>>();
+  assert_send_static::<UserLogsGet>();
+}
+
+#[tokio::test]
+async fn test_await_builder() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let users = auth0.users.find().page(0).per_page(2).await.unwrap();
+  assert_eq!(users.len(), 2);
+
+  let user = auth0
+    .users
+    .update(UserId::new("auth0|1"))
+    .email("test@example.test")
+    .await
+    .unwrap();
+  assert_eq!(user.user_id, "auth0|1");
+  assert_eq!(user.app_metadata(), Some(&json!({ "plan": "pro" })));
+
+  let user = auth0
+    .users
+    .create()
+    .connection("Username-Password-Authentication")
+    .email("test@example.test")
+    .await
+    .unwrap();
+  assert_eq!(user.user_id, "auth0|new");
+}
+
+#[tokio::test]
+async fn test_spawn_queued_builders() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let queue = (1..=3)
+    .map(|i| {
+      let mut update = auth0.users.update(UserId::new(format!("auth0|{}", i)));
+      update.blocked(true);
+      update
+    })
+    .collect::<Vec<_>>();
+
+  drop(auth0);
+
+  let handles = queue
+    .into_iter()
+    .map(|update| tokio::spawn(update.into_future()))
+    .collect::<Vec<_>>();
+  let mut users: Vec<User<Value, Value>> = Vec::new();
+  for handle in handles {
+    users.push(handle.await.unwrap().unwrap());
+  }
+
+  assert_eq!(users[2].user_id, "auth0|3");
+  assert_eq!(server.requests_to("/api/v2/users/auth0%7C2").len(), 1);
+}
+
+/// Metadata which isn't `Clone`.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Plan {
+  plan: String,
+}
+
+#[tokio::test]
+async fn test_await_builder_without_clone_metadata() {
+  let server = tenant_server();
+  let auth0 = server.auth0().with_metadata::<Plan, Value>();
+
+  let mut update = auth0.users.update(UserId::new("auth0|1"));
+  update.app_metadata(Plan {
+    plan: "pro".to_owned(),
+  });
+  let user = update.await.unwrap();
+  assert_eq!(
+    user.app_metadata(),
+    Some(&Plan {
+      plan: "pro".to_owned()
+    })
+  );
+
+  let body: Value =
+    serde_json::from_str(&server.requests_to("/api/v2/users/auth0%7C1")[0].body).unwrap();
+  assert_eq!(body, json!({ "app_metadata": { "plan": "pro" } }));
+}
+
+#[tokio::test]
+async fn test_await_same_builder_twice() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let mut update = auth0.users.update(UserId::new("auth0|1"));
+  update
+    .email("test@example.test")
+    .app_metadata(json!({ "plan": "pro" }));
+  (&mut update).await.unwrap();
+  (&mut update).await.unwrap();
+
+  let mut create = auth0.users.create();
+  create
+    .connection("Username-Password-Authentication")
+    .email("test@example.test");
+  (&mut create).await.unwrap();
+  (&mut create).await.unwrap();
+
+  let bodies = |path| {
+    server
+      .requests_to(path)
+      .iter()
+      .map(|req| serde_json::from_str::<Value>(&req.body).unwrap())
+      .collect::<Vec<_>>()
+  };
+  let expected =
+    json!({ "email": "test@example.test", "app_metadata": { "plan": "pro" } });
+  assert_eq!(
+    bodies("/api/v2/users/auth0%7C1"),
+    vec![expected.clone(), expected]
+  );
+  let expected = json!({
+    "connection": "Username-Password-Authentication",
+    "email": "test@example.test"
+  });
+  assert_eq!(bodies("/api/v2/users"), vec![expected.clone(), expected]);
+}
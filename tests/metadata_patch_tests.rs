@@ -0,0 +1,193 @@
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use auth0_management::{Auth0Error, MetadataPatch, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+/// Start tenant storing the app metadata of `auth0|1`, `clobber` is called after every
+/// update to simulate concurrent writes.
+fn tenant_server<F>(clobber: F) -> MockServer
+where
+  F: Fn(usize, &mut Map<String, Value>) + Send + Sync + 'static,
+{
+  let metadata = Arc::new(Mutex::new(
+    json!({ "prefs": { "theme": "light", "lang": "en" } })
+      .as_object()
+      .unwrap()
+      .clone(),
+  ));
+  let updates = Mutex::new(0);
+
+  MockServer::tenant(move |req| {
+    let mut metadata = metadata.lock().unwrap();
+    if req.method == "PATCH" {
+      let body: Value = serde_json::from_str(&req.body).unwrap();
+      for (key, value) in body["app_metadata"].as_object().unwrap() {
+        match value {
+          Value::Null => metadata.remove(key),
+          value => metadata.insert(key.clone(), value.clone()),
+        };
+      }
+
+      let mut updates = updates.lock().unwrap();
+      *updates += 1;
+      clobber(*updates, &mut metadata);
+    }
+
+    MockResponse::json(
+      200,
+      json!({ "user_id": "auth0|1", "app_metadata": Value::Object(metadata.clone()) }),
+    )
+    .with_rate_limit()
+  })
+}
+
+fn methods(server: &MockServer) -> Vec<String> {
+  server
+    .requests_to("/api/v2/users/auth0%7C1")
+    .into_iter()
+    .map(|req| req.method)
+    .collect()
+}
+
+#[tokio::test]
+async fn test_patch_metadata() {
+  let server = tenant_server(|_, _| {});
+  let auth0 = server.auth0();
+
+  let user = auth0
+    .users
+    .patch_metadata(UserId::new("auth0|1"))
+    .app_metadata(
+      MetadataPatch::new()
+        .set_path(&["prefs", "theme"], "dark")
+        .clone(),
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    user.app_metadata(),
+    Some(&json!({ "prefs": { "theme": "dark", "lang": "en" } }))
+  );
+  assert_eq!(methods(&server), ["GET", "PATCH", "GET"]);
+}
+
+#[tokio::test]
+async fn test_patch_metadata_retry() {
+  // A concurrent write replaces the preferences after the first update only.
+  let server = tenant_server(|updates, metadata| {
+    if updates == 1 {
+      metadata.insert("prefs".to_owned(), json!({ "lang": "de" }));
+    }
+  });
+  let auth0 = server.auth0();
+
+  let user = auth0
+    .users
+    .patch_metadata(UserId::new("auth0|1"))
+    .app_metadata(
+      MetadataPatch::new()
+        .set_path(&["prefs", "theme"], "dark")
+        .clone(),
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    user.app_metadata(),
+    Some(&json!({ "prefs": { "theme": "dark", "lang": "de" } }))
+  );
+  assert_eq!(methods(&server), ["GET", "PATCH", "GET", "PATCH", "GET"]);
+
+  let updates = server
+    .requests_to("/api/v2/users/auth0%7C1")
+    .into_iter()
+    .filter(|req| req.method == "PATCH")
+    .map(|req| serde_json::from_str::<Value>(&req.body).unwrap())
+    .collect::<Vec<_>>();
+  assert_eq!(
+    updates[1],
+    json!({ "app_metadata": { "prefs": { "theme": "dark", "lang": "de" } } })
+  );
+}
+
+#[tokio::test]
+async fn test_patch_metadata_lost_sibling() {
+  // A concurrent write drops the language next to the written theme after the first update.
+  let server = tenant_server(|updates, metadata| {
+    if updates == 1 {
+      metadata.insert("prefs".to_owned(), json!({ "theme": "dark" }));
+    }
+  });
+  let auth0 = server.auth0();
+
+  let user = auth0
+    .users
+    .patch_metadata(UserId::new("auth0|1"))
+    .app_metadata(
+      MetadataPatch::new()
+        .set_path(&["prefs", "theme"], "dark")
+        .clone(),
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(
+    user.app_metadata(),
+    Some(&json!({ "prefs": { "theme": "dark" } }))
+  );
+  assert_eq!(methods(&server), ["GET", "PATCH", "GET", "PATCH", "GET"]);
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Prefs {
+  prefs: Map<String, Value>,
+}
+
+#[tokio::test]
+async fn test_patch_metadata_bound_metadata() {
+  let server = tenant_server(|_, _| {});
+  let auth0 = server.auth0().with_metadata::<Prefs, Value>();
+
+  let user = auth0
+    .users
+    .patch_metadata(UserId::new("auth0|1"))
+    .app_metadata(
+      MetadataPatch::new()
+        .set_path(&["prefs", "lang"], "de")
+        .clone(),
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(user.app_metadata().unwrap().prefs["lang"], json!("de"));
+}
+
+#[tokio::test]
+async fn test_patch_metadata_conflict() {
+  // A concurrent write replaces the preferences after every update.
+  let server = tenant_server(|_, metadata| {
+    metadata.insert("prefs".to_owned(), json!({ "theme": "light" }));
+  });
+  let auth0 = server.auth0();
+
+  let res = auth0
+    .users
+    .patch_metadata(UserId::new("auth0|1"))
+    .app_metadata(
+      MetadataPatch::new()
+        .set_path(&["prefs", "theme"], "dark")
+        .clone(),
+    )
+    .retries(1)
+    .await;
+
+  assert!(matches!(res, Err(Auth0Error::Conflict(id)) if id == "auth0|1"));
+  assert_eq!(methods(&server), ["GET", "PATCH", "GET", "PATCH", "GET"]);
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use auth0_management::{Auth0, Pageable, User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct AppMeta {
+  plan: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct UserMeta {
+  theme: String,
+}
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    let user = json!({
+      "user_id": "auth0|1",
+      "app_metadata": { "plan": "pro" },
+      "user_metadata": { "theme": "dark" },
+    });
+    let body = if req.method == "GET"
+      && (req.path.starts_with("/api/v2/users?")
+        || req.path == "/api/v2/users"
+        || req.path.starts_with("/api/v2/users-by-email"))
+    {
+      json!([user])
+    } else {
+      user
+    };
+
+    MockResponse::json(200, body).with_rate_limit()
+  })
+}
+
+fn assert_typed(user: &User<AppMeta, UserMeta>) {
+  assert_eq!(
+    user.app_metadata(),
+    Some(&AppMeta {
+      plan: "pro".to_owned()
+    })
+  );
+  assert_eq!(
+    user.user_metadata(),
+    Some(&UserMeta {
+      theme: "dark".to_owned()
+    })
+  );
+}
+
+#[tokio::test]
+async fn test_bound_metadata() {
+  let server = tenant_server();
+  let auth0: Auth0<AppMeta, UserMeta> = server.auth0().with_metadata();
+
+  assert_typed(&auth0.users.get(UserId::new("auth0|1")).await.unwrap());
+  assert_typed(&auth0.users.find().per_page(1).await.unwrap()[0]);
+  assert_typed(
+    &auth0
+      .users
+      .find_by_email("test@example.test")
+      .await
+      .unwrap()[0],
+  );
+
+  let created = auth0
+    .users
+    .create()
+    .connection("CONNECTION_ID")
+    .app_metadata(AppMeta {
+      plan: "pro".to_owned(),
+    })
+    .user_metadata(UserMeta {
+      theme: "dark".to_owned(),
+    })
+    .await
+    .unwrap();
+  assert_typed(&created);
+
+  let updated = auth0
+    .users
+    .update(UserId::new("auth0|1"))
+    .app_metadata(AppMeta {
+      plan: "pro".to_owned(),
+    })
+    .await
+    .unwrap();
+  assert_typed(&updated);
+
+  let create = server
+    .requests_to("/api/v2/users")
+    .into_iter()
+    .find(|req| req.method == "POST")
+    .unwrap();
+  let body: Value = serde_json::from_str(&create.body).unwrap();
+  assert_eq!(body["app_metadata"], json!({ "plan": "pro" }));
+  assert_eq!(body["user_metadata"], json!({ "theme": "dark" }));
+}
+
+#[tokio::test]
+async fn test_untyped_metadata() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let user = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+  assert_eq!(user.app_metadata(), Some(&json!({ "plan": "pro" })));
+
+  let users = auth0.users.find().await.unwrap();
+  assert_eq!(users[0].user_metadata(), Some(&json!({ "theme": "dark" })));
+
+  let typed = auth0
+    .users
+    .find()
+    .send::<AppMeta, UserMeta>()
+    .await
+    .unwrap();
+  assert_typed(&typed[0]);
+}
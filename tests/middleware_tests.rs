@@ -0,0 +1,166 @@
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::HeaderValue;
+use reqwest::{Method, StatusCode};
+use serde_json::{json, Value};
+use url::Url;
+
+use auth0_management::{
+  Auth0, Exchange, HttpBody, HttpRequest, Middleware, User, UserId,
+};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+/// Method, path, status and whether `before_send` ran.
+type Record = (Method, String, Option<StatusCode>, bool);
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Record>>>);
+
+impl Middleware for Recorder {
+  fn before_send(&self, req: &mut HttpRequest) {
+    req
+      .headers
+      .insert("x-request-id", HeaderValue::from_static("REQUEST_ID"));
+  }
+
+  fn after_receive(&self, exchange: &Exchange<'_>) {
+    self.0.lock().unwrap().push((
+      exchange.method.clone(),
+      Url::parse(exchange.url).unwrap().path().to_owned(),
+      exchange.status,
+      exchange.request_headers.contains_key("x-request-id"),
+    ));
+  }
+}
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    if req.path == "/api/v2/users/missing" {
+      return MockResponse::json(404, json!({ "message": "not found" }));
+    }
+
+    MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+  })
+}
+
+#[tokio::test]
+async fn test_middleware_sees_every_request() {
+  let server = tenant_server();
+  let recorder = Recorder::default();
+  let auth0 = server
+    .builder()
+    .middleware(recorder.clone())
+    .build()
+    .unwrap();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("1")).await.unwrap();
+  let _ = auth0.users.get(UserId::new("missing")).await;
+
+  let exchanges = recorder.0.lock().unwrap().clone();
+  assert_eq!(
+    exchanges,
+    vec![
+      (
+        Method::POST,
+        "/oauth/token".to_owned(),
+        Some(StatusCode::OK),
+        true
+      ),
+      (
+        Method::GET,
+        "/api/v2/users/1".to_owned(),
+        Some(StatusCode::OK),
+        true
+      ),
+      (
+        Method::GET,
+        "/api/v2/users/missing".to_owned(),
+        Some(StatusCode::NOT_FOUND),
+        true
+      ),
+    ]
+  );
+  assert!(server
+    .requests()
+    .iter()
+    .all(|req| req.header("x-request-id") == Some("REQUEST_ID")));
+}
+
+#[tokio::test]
+async fn test_middleware_sees_failed_requests() {
+  let recorder = Recorder::default();
+  let auth0 = Auth0::builder()
+    .domain("http://127.0.0.1:1")
+    .client_id("CLIENT_ID")
+    .client_secret("CLIENT_SECRET")
+    .middleware(recorder.clone())
+    .build()
+    .unwrap();
+
+  assert!(auth0.users.get(UserId::new("auth0|1")).await.is_err());
+
+  let exchanges = recorder.0.lock().unwrap().clone();
+  assert_eq!(exchanges.len(), 1);
+  assert_eq!(exchanges[0].2, None);
+}
+
+/// Authorization header, request body and response body.
+type BodyRecord = (Option<String>, Option<HttpBody>, Option<Value>);
+
+#[derive(Clone, Default)]
+struct BodyRecorder(Arc<Mutex<Vec<BodyRecord>>>);
+
+impl Middleware for BodyRecorder {
+  fn after_receive(&self, exchange: &Exchange<'_>) {
+    self.0.lock().unwrap().push((
+      exchange
+        .request_headers
+        .get("authorization")
+        .map(|value| value.to_str().unwrap().to_owned()),
+      exchange.request_body.cloned(),
+      exchange
+        .response_body
+        .map(|body| serde_json::from_slice(body).unwrap()),
+    ));
+  }
+}
+
+#[tokio::test]
+async fn test_middleware_sees_redacted_bodies() {
+  let server = tenant_server();
+  let recorder = BodyRecorder::default();
+  let auth0 = server
+    .builder()
+    .middleware(recorder.clone())
+    .build()
+    .unwrap();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("1")).await.unwrap();
+
+  let exchanges = recorder.0.lock().unwrap().clone();
+  assert_eq!(exchanges.len(), 2);
+
+  let (authorization, body, response) = &exchanges[0];
+  assert_eq!(authorization, &None);
+  match body {
+    Some(HttpBody::Form(form)) => {
+      assert!(form.contains(&("client_id".to_owned(), "CLIENT_ID".to_owned())));
+      assert!(form.contains(&("client_secret".to_owned(), "[REDACTED]".to_owned())));
+    }
+    body => panic!("unexpected token request body {:?}", body),
+  }
+  assert_eq!(response.as_ref().unwrap()["access_token"], "[REDACTED]");
+
+  let (authorization, body, response) = &exchanges[1];
+  assert_eq!(authorization.as_deref(), Some("[REDACTED]"));
+  assert_eq!(body, &None);
+  assert_eq!(response, &Some(json!({ "user_id": "auth0|1" })));
+
+  assert!(server
+    .requests_to("/api/v2/users/1")
+    .iter()
+    .all(|req| req.header("authorization") != Some("[REDACTED]")));
+}
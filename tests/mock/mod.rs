@@ -0,0 +1,221 @@
+//! Minimal HTTP/1.1 server standing in for an Auth0 tenant.
+#![allow(dead_code)]
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use auth0_management::{Auth0, Auth0Builder};
+
+/// Request received by the mock server.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+  pub method: String,
+  pub path: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl MockRequest {
+  /// Get header value by case-insensitive name.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(key, _)| key.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Get url encoded form value of body.
+  pub fn form(&self, name: &str) -> Option<String> {
+    url::form_urlencoded::parse(self.body.as_bytes())
+      .find(|(key, _)| key == name)
+      .map(|(_, value)| value.into_owned())
+  }
+}
+
+/// Response returned by the mock server.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+  pub status: u16,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl MockResponse {
+  /// Create json response.
+  pub fn json(status: u16, body: serde_json::Value) -> Self {
+    Self {
+      status,
+      headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+      body: body.to_string(),
+    }
+  }
+
+  /// Create client credentials token response.
+  pub fn token(access_token: &str) -> Self {
+    Self::json(
+      200,
+      serde_json::json!({ "access_token": access_token, "expires_in": 3600 }),
+    )
+  }
+
+  /// Add response header.
+  pub fn header(mut self, name: &str, value: &str) -> Self {
+    self.headers.push((name.to_owned(), value.to_owned()));
+    self
+  }
+
+  /// Add rate limit headers with requests remaining.
+  pub fn with_rate_limit(self) -> Self {
+    self
+      .header("x-ratelimit-limit", "50")
+      .header("x-ratelimit-remaining", "49")
+      .header("x-ratelimit-reset", "0")
+  }
+}
+
+/// Mock server accepting connections on a background thread.
+pub struct MockServer {
+  addr: SocketAddr,
+  requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+  /// Start server answering every request with `respond`.
+  pub fn start<F>(respond: F) -> Self
+  where
+    F: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+  {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let respond = Arc::new(respond);
+
+    {
+      let requests = requests.clone();
+      thread::spawn(move || {
+        for stream in listener.incoming() {
+          let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => break,
+          };
+          let requests = requests.clone();
+          let respond = respond.clone();
+
+          thread::spawn(move || handle(stream, &requests, &*respond));
+        }
+      });
+    }
+
+    Self { addr, requests }
+  }
+
+  /// Start server standing in for a tenant, token requests are answered with `TOKEN` and
+  /// every other request with `respond`.
+  pub fn tenant<F>(respond: F) -> Self
+  where
+    F: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+  {
+    Self::start(move |req| {
+      if req.path == "/oauth/token" {
+        MockResponse::token("TOKEN")
+      } else {
+        respond(req)
+      }
+    })
+  }
+
+  /// Builder of a client for this server, authenticating with client credentials.
+  pub fn builder(&self) -> Auth0Builder {
+    Auth0::builder()
+      .domain(&self.domain())
+      .client_id("CLIENT_ID")
+      .client_secret("CLIENT_SECRET")
+  }
+
+  /// Client for this server, authenticating with client credentials.
+  pub fn auth0(&self) -> Auth0 {
+    self.builder().build().unwrap()
+  }
+
+  /// Domain to configure the client with.
+  pub fn domain(&self) -> String {
+    format!("http://{}", self.addr)
+  }
+
+  /// Requests received so far.
+  pub fn requests(&self) -> Vec<MockRequest> {
+    self.requests.lock().unwrap().clone()
+  }
+
+  /// Requests received so far for `path`.
+  pub fn requests_to(&self, path: &str) -> Vec<MockRequest> {
+    self
+      .requests()
+      .into_iter()
+      .filter(|req| req.path == path)
+      .collect()
+  }
+}
+
+fn handle(
+  stream: TcpStream,
+  requests: &Mutex<Vec<MockRequest>>,
+  respond: &dyn Fn(&MockRequest) -> MockResponse,
+) {
+  let mut reader = BufReader::new(stream.try_clone().unwrap());
+  let mut stream = stream;
+
+  loop {
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+      return;
+    }
+
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut headers = Vec::new();
+    loop {
+      let mut line = String::new();
+      reader.read_line(&mut line).unwrap();
+      let line = line.trim_end();
+      if line.is_empty() {
+        break;
+      }
+
+      let (name, value) = line.split_at(line.find(':').unwrap());
+      headers.push((name.to_owned(), value[1..].trim().to_owned()));
+    }
+
+    let len = headers
+      .iter()
+      .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+      .map(|(_, value)| value.parse().unwrap())
+      .unwrap_or(0);
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).unwrap();
+
+    let req = MockRequest {
+      method,
+      path,
+      headers,
+      body: String::from_utf8(body).unwrap(),
+    };
+    let res = respond(&req);
+    requests.lock().unwrap().push(req);
+
+    let mut out = format!("HTTP/1.1 {} MOCK\r\n", res.status);
+    for (name, value) in &res.headers {
+      out.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    out.push_str(&format!("content-length: {}\r\n\r\n", res.body.len()));
+    out.push_str(&res.body);
+
+    if stream.write_all(out.as_bytes()).is_err() {
+      return;
+    }
+  }
+}
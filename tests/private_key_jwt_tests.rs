@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use auth0_management::token::{
+  ClientCredentials, PrivateKeyJwt, SigningAlgorithm, TokenManager, CLIENT_ASSERTION_TYPE,
+};
+use auth0_management::{Auth0, Auth0BuilderError};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+const RSA_PRIVATE: &str = include_str!("fixtures/keys/rsa_private.pem");
+const RSA_PUBLIC: &str = include_str!("fixtures/keys/rsa_public.pem");
+const EC_PRIVATE: &str = include_str!("fixtures/keys/ec_private.pem");
+const EC_PUBLIC: &str = include_str!("fixtures/keys/ec_public.pem");
+
+#[derive(Deserialize)]
+struct Claims {
+  iss: String,
+  sub: String,
+  jti: String,
+}
+
+fn token_server() -> MockServer {
+  MockServer::start(|_| {
+    MockResponse::json(
+      200,
+      json!({ "access_token": "ACCESS_TOKEN", "expires_in": 0, "token_type": "Bearer" }),
+    )
+  })
+}
+
+fn token_manager(server: &MockServer, key: PrivateKeyJwt) -> TokenManager {
+  TokenManager::new(
+    Client::new(),
+    &server.domain(),
+    "https://example.auth0.com/api/v2/",
+    "CLIENT_ID",
+    ClientCredentials::PrivateKey(key),
+  )
+}
+
+async fn assert_assertions(
+  algorithm: SigningAlgorithm,
+  private: &str,
+  public: DecodingKey,
+) {
+  let server = token_server();
+  let key = PrivateKeyJwt::from_pem(private.as_bytes(), "KEY_ID", algorithm).unwrap();
+  let manager = token_manager(&server, key);
+
+  // Expired tokens are fetched again, every fetch must carry a fresh assertion.
+  assert_eq!(manager.get_token().await.unwrap(), "ACCESS_TOKEN");
+  assert_eq!(manager.get_token().await.unwrap(), "ACCESS_TOKEN");
+
+  let audience = format!("{}/", server.domain());
+  let requests = server.requests_to("/oauth/token");
+  let mut ids = HashSet::new();
+
+  assert_eq!(requests.len(), 2);
+  for req in requests {
+    assert_eq!(req.method, "POST");
+    assert_eq!(req.form("client_secret"), None);
+    assert_eq!(req.form("client_id").unwrap(), "CLIENT_ID");
+    assert_eq!(req.form("grant_type").unwrap(), "client_credentials");
+    assert_eq!(
+      req.form("client_assertion_type").unwrap(),
+      CLIENT_ASSERTION_TYPE
+    );
+
+    let assertion = req.form("client_assertion").unwrap();
+    let header = decode_header(&assertion).unwrap();
+    let mut validation = Validation::new(header.alg);
+    validation.set_audience(&[&audience]);
+    validation.set_issuer(&["CLIENT_ID"]);
+
+    let claims = decode::<Claims>(&assertion, &public, &validation)
+      .unwrap()
+      .claims;
+
+    assert_eq!(header.alg, algorithm_of(algorithm));
+    assert_eq!(header.kid.as_deref(), Some("KEY_ID"));
+    assert_eq!(claims.iss, "CLIENT_ID");
+    assert_eq!(claims.sub, "CLIENT_ID");
+    assert!(ids.insert(claims.jti));
+  }
+}
+
+fn algorithm_of(algorithm: SigningAlgorithm) -> Algorithm {
+  match algorithm {
+    SigningAlgorithm::RS256 => Algorithm::RS256,
+    SigningAlgorithm::PS256 => Algorithm::PS256,
+    SigningAlgorithm::ES256 => Algorithm::ES256,
+  }
+}
+
+#[tokio::test]
+async fn test_rs256_assertion() {
+  let public = DecodingKey::from_rsa_pem(RSA_PUBLIC.as_bytes()).unwrap();
+  assert_assertions(SigningAlgorithm::RS256, RSA_PRIVATE, public).await;
+}
+
+#[tokio::test]
+async fn test_ps256_assertion() {
+  let public = DecodingKey::from_rsa_pem(RSA_PUBLIC.as_bytes()).unwrap();
+  assert_assertions(SigningAlgorithm::PS256, RSA_PRIVATE, public).await;
+}
+
+#[tokio::test]
+async fn test_es256_assertion() {
+  let public = DecodingKey::from_ec_pem(EC_PUBLIC.as_bytes()).unwrap();
+  assert_assertions(SigningAlgorithm::ES256, EC_PRIVATE, public).await;
+}
+
+#[tokio::test]
+async fn test_client_secret() {
+  let server = token_server();
+  let manager = TokenManager::new(
+    Client::new(),
+    &server.domain(),
+    "https://example.auth0.com/api/v2/",
+    "CLIENT_ID",
+    ClientCredentials::Secret("CLIENT_SECRET".to_owned()),
+  );
+
+  manager.get_token().await.unwrap();
+
+  let req = &server.requests_to("/oauth/token")[0];
+  assert_eq!(req.form("client_secret").unwrap(), "CLIENT_SECRET");
+  assert_eq!(req.form("client_assertion"), None);
+}
+
+#[test]
+fn test_invalid_private_key() {
+  let res = Auth0::builder()
+    .domain("example.auth0.com")
+    .audience("https://example.auth0.com/api/v2/")
+    .client_id("CLIENT_ID")
+    .private_key("not a key", "KEY_ID")
+    .build();
+  assert_eq!(res.err(), Some(Auth0BuilderError::InvalidPrivateKey));
+
+  let res = Auth0::builder()
+    .domain("example.auth0.com")
+    .audience("https://example.auth0.com/api/v2/")
+    .client_id("CLIENT_ID")
+    .private_key(RSA_PRIVATE, "KEY_ID")
+    .signing_algorithm(SigningAlgorithm::ES256)
+    .build();
+  assert_eq!(res.err(), Some(Auth0BuilderError::InvalidPrivateKey));
+}
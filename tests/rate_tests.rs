@@ -1,22 +1,123 @@
-// use auth0_management::rate::RateLimit;
-// use std::time::{Duration, SystemTime};
-//
-// #[test]
-// fn test_check_limit() {
-//   let mut rate = RateLimit::new();
-//
-//   assert!(rate.check_limit());
-//
-//   rate.limit = 2;
-//   rate.reset_secs = SystemTime::now() + Duration::from_secs(1000);
-//   rate.remaining = 2;
-//
-//   assert!(rate.check_limit());
-//   assert!(rate.check_limit());
-//   assert!(!rate.check_limit());
-//
-//   rate.reset_secs = SystemTime::now();
-//
-//   assert!(rate.check_limit());
-//   assert!(rate.check_limit());
-// }
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::HeaderMap;
+use serde_json::{json, Value};
+
+use auth0_management::rate::RateLimit;
+use auth0_management::{Auth0Error, User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn headers(limit: u32, remaining: u32, reset: SystemTime) -> HeaderMap {
+  let reset = reset.duration_since(UNIX_EPOCH).unwrap().as_secs();
+  let mut headers = HeaderMap::new();
+
+  headers.insert("x-ratelimit-limit", limit.into());
+  headers.insert("x-ratelimit-remaining", remaining.into());
+  headers.insert("x-ratelimit-reset", reset.into());
+  headers
+}
+
+#[test]
+fn test_check_limit() {
+  let rate = RateLimit::new();
+
+  assert!(rate.check_limit());
+
+  rate
+    .read(&headers(
+      2,
+      2,
+      SystemTime::now() + Duration::from_secs(1000),
+    ))
+    .unwrap();
+
+  assert!(rate.check_limit());
+  assert!(rate.check_limit());
+  assert!(!rate.check_limit());
+
+  rate
+    .read(&headers(2, 0, SystemTime::now() - Duration::from_secs(1)))
+    .unwrap();
+
+  assert!(rate.check_limit());
+  assert!(rate.check_limit());
+}
+
+#[test]
+fn test_check_limit_concurrent() {
+  let rate = Arc::new(RateLimit::new());
+  rate
+    .read(&headers(
+      100,
+      100,
+      SystemTime::now() + Duration::from_secs(1000),
+    ))
+    .unwrap();
+
+  let threads = (0..8)
+    .map(|_| {
+      let rate = rate.clone();
+      thread::spawn(move || (0..50).filter(|_| rate.check_limit()).count())
+    })
+    .collect::<Vec<_>>();
+
+  let allowed: usize = threads.into_iter().map(|t| t.join().unwrap()).sum();
+  assert_eq!(allowed, 100);
+}
+
+#[test]
+fn test_read_missing_headers() {
+  assert!(RateLimit::new().read(&HeaderMap::new()).is_err());
+}
+
+#[test]
+fn test_wait_time() {
+  let rate = RateLimit::new();
+  let reset = SystemTime::now() + Duration::from_secs(1000);
+
+  rate.read(&headers(2, 1, reset)).unwrap();
+
+  assert_eq!(rate.wait_time(), None);
+  let wait = rate.wait_time().unwrap();
+  assert!(wait > Duration::from_secs(990) && wait <= Duration::from_secs(1000));
+
+  rate
+    .read(&headers(2, 0, SystemTime::now() - Duration::from_secs(1)))
+    .unwrap();
+
+  assert_eq!(rate.wait_time(), None);
+}
+
+#[tokio::test]
+async fn test_requests_wait_for_reset() {
+  let reset = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs()
+    + 2;
+  let limited = AtomicBool::new(false);
+  let server = MockServer::tenant(move |_| {
+    if limited.swap(true, Ordering::SeqCst) {
+      MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+    } else {
+      MockResponse::json(429, json!({ "message": "Too Many Requests" }))
+        .header("x-ratelimit-limit", "50")
+        .header("x-ratelimit-remaining", "0")
+        .header("x-ratelimit-reset", &reset.to_string())
+    }
+  });
+  let auth0 = server.auth0();
+
+  let res: Result<User<Value, Value>, _> = auth0.users.get(UserId::new("auth0|1")).await;
+  assert!(matches!(res, Err(Auth0Error::Auth0(_))));
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+  assert!(SystemTime::now() >= UNIX_EPOCH + Duration::from_secs(reset));
+  assert_eq!(server.requests_to("/api/v2/users/auth0%7C1").len(), 2);
+}
@@ -0,0 +1,479 @@
+use std::sync::Arc;
+
+use reqwest::Method;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use auth0_management::rate::RateLimit;
+use auth0_management::{
+  Auth0Client, Auth0RequestBuilder, ClientId, ConnectionId,
+  EmailVerificationTicketCreate, HttpRequest, LogSortField, Ordering, OrgId, Pageable,
+  PasswordChangeTicketCreate, Permission, Sortable, StaticToken, UserBlocksDelete,
+  UserBlocksDeleteByIdentifier, UserBlocksGet, UserBlocksGetByIdentifier, UserCreate,
+  UserDelete, UserEnrollmentsGet, UserGet, UserId, UserLogsGet, UserPermissionsDelete,
+  UserPermissionsGet, UserPermissionsUpdate, UserSortField, UserUpdate, UsersFind,
+  UsersFindByEmail,
+};
+
+const BASE: &str = "https://example.auth0.com/api/v2";
+
+fn client() -> Arc<Auth0Client> {
+  Arc::new(Auth0Client::new(
+    RateLimit::new(),
+    StaticToken::new("TOKEN"),
+    reqwest::Client::new(),
+    "example.auth0.com",
+  ))
+}
+
+fn assert_request(
+  req: &HttpRequest,
+  method: Method,
+  path: &str,
+  query: &[(&str, &str)],
+  body: Option<Value>,
+) {
+  let query = query
+    .iter()
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect::<Vec<_>>();
+
+  assert_eq!(req.method, method);
+  assert_eq!(req.url, format!("{}{}", BASE, path));
+  assert_eq!(req.query, query);
+  assert_eq!(req.json_body(), body.as_ref());
+}
+
+fn permission(name: &str) -> Permission {
+  Permission {
+    name: name.to_owned(),
+    description: String::new(),
+    resource_server_name: "API".to_owned(),
+    resource_server_identifier: "https://api.example.test".to_owned(),
+  }
+}
+
+#[test]
+fn test_users_find() {
+  let client = client();
+
+  let req = UsersFind::new(client.clone()).build(&client);
+  assert_request(&req, Method::GET, "/users", &[], None);
+
+  let req = UsersFind::new(client.clone())
+    .page(2)
+    .per_page(25)
+    .sort(UserSortField::Email, Ordering::Ascending)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users",
+    &[("page", "2"), ("per_page", "25"), ("sort", "email:1")],
+    None,
+  );
+
+  let req = UsersFind::new(client.clone())
+    .sort(UserSortField::CreatedAt, Ordering::Descending)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users",
+    &[("sort", "created_at:-1")],
+    None,
+  );
+}
+
+#[test]
+fn test_users_find_by_email() {
+  let client = client();
+
+  let req = UsersFindByEmail::new(client.clone(), "test@example.test")
+    .fields(&["email", "user_id"])
+    .include_fields(true)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users-by-email",
+    &[
+      ("email", "test@example.test"),
+      ("fields", "email,user_id"),
+      ("include_fields", "true"),
+    ],
+    None,
+  );
+
+  let req = UsersFindByEmail::new(client.clone(), "Test@Example.test").build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users-by-email",
+    &[("email", "Test@Example.test")],
+    None,
+  );
+
+  let req = UsersFindByEmail::new(client.clone(), "Test@Example.test")
+    .case_insensitive(true)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users-by-email",
+    &[("email", "Test@Example.test")],
+    None,
+  );
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_get_user_by_email_deprecated() {
+  let client = client();
+
+  let req = auth0_management::GetUserByEmail::new("test@example.test").build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users-by-email",
+    &[("email", "test@example.test")],
+    None,
+  );
+}
+
+#[test]
+fn test_user_get() {
+  let req = UserGet::new(UserId::new("auth0|1")).build(&client());
+  assert_request(&req, Method::GET, "/users/auth0%7C1", &[], None);
+
+  let req = UserGet::new(UserId::from_parts("google-oauth2", "123")).build(&client());
+  assert_request(&req, Method::GET, "/users/google-oauth2%7C123", &[], None);
+}
+
+#[test]
+fn test_user_create() {
+  let client = client();
+
+  let req = UserCreate::new(client.clone())
+    .connection("Username-Password-Authentication")
+    .email("test@example.test")
+    .password("PASSWORD")
+    .email_verified(true)
+    .verify_email(false)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/users",
+    &[],
+    Some(json!({
+      "connection": "Username-Password-Authentication",
+      "email": "test@example.test",
+      "password": "PASSWORD",
+      "email_verified": true,
+      "verify_email": false,
+    })),
+  );
+
+  let req = UserCreate::new(client.clone())
+    .connection("sms")
+    .phone_number("+15555550100")
+    .app_metadata(json!({ "plan": "free" }))
+    .user_metadata(json!({ "theme": "dark" }))
+    .build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/users",
+    &[],
+    Some(json!({
+      "connection": "sms",
+      "phone_number": "+15555550100",
+      "app_metadata": { "plan": "free" },
+      "user_metadata": { "theme": "dark" },
+    })),
+  );
+}
+
+#[derive(Serialize, Clone)]
+struct Preferences {
+  theme: &'static str,
+  beta: bool,
+}
+
+#[test]
+fn test_user_create_typed_metadata() {
+  let client = client();
+
+  let mut create = UserCreate::new(client.clone())
+    .with_user_metadata(Preferences {
+      theme: "dark",
+      beta: false,
+    })
+    .with_app_metadata(json!({ "plan": "free" }));
+  create.connection("sms").user_metadata(Preferences {
+    theme: "light",
+    beta: true,
+  });
+
+  let req = create.build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/users",
+    &[],
+    Some(json!({
+      "connection": "sms",
+      "app_metadata": { "plan": "free" },
+      "user_metadata": { "theme": "light", "beta": true },
+    })),
+  );
+}
+
+#[test]
+fn test_user_update() {
+  let client = client();
+
+  let req = UserUpdate::new(client.clone(), UserId::new("auth0|1"))
+    .blocked(true)
+    .name("Test User")
+    .build(&client);
+  assert_request(
+    &req,
+    Method::PATCH,
+    "/users/auth0%7C1",
+    &[],
+    Some(json!({ "blocked": true, "name": "Test User" })),
+  );
+
+  let req = UserUpdate::new(client.clone(), UserId::new("auth0|1"))
+    .email("test@example.test")
+    .client_id(ClientId::new("CLIENT_ID"))
+    .app_metadata(json!({ "plan": "pro" }))
+    .build(&client);
+  assert_request(
+    &req,
+    Method::PATCH,
+    "/users/auth0%7C1",
+    &[],
+    Some(json!({
+      "email": "test@example.test",
+      "client_id": "CLIENT_ID",
+      "app_metadata": { "plan": "pro" },
+    })),
+  );
+}
+
+#[test]
+fn test_user_update_typed_metadata() {
+  let client = client();
+
+  let req = UserUpdate::new(client.clone(), UserId::new("auth0|1"))
+    .with_user_metadata(Preferences {
+      theme: "dark",
+      beta: true,
+    })
+    .name("Test User")
+    .build(&client);
+  assert_request(
+    &req,
+    Method::PATCH,
+    "/users/auth0%7C1",
+    &[],
+    Some(json!({
+      "name": "Test User",
+      "user_metadata": { "theme": "dark", "beta": true },
+    })),
+  );
+}
+
+#[test]
+fn test_user_delete() {
+  let req = UserDelete::new(UserId::new("auth0|1")).build(&client());
+  assert_request(&req, Method::DELETE, "/users/auth0%7C1", &[], None);
+}
+
+#[test]
+fn test_user_logs_get() {
+  let client = client();
+
+  let req = UserLogsGet::new(client.clone(), UserId::new("auth0|1")).build(&client);
+  assert_request(&req, Method::GET, "/users/auth0%7C1/logs", &[], None);
+
+  let req = UserLogsGet::new(client.clone(), UserId::new("auth0|1"))
+    .page(0)
+    .per_page(50)
+    .sort(LogSortField::Date, Ordering::Descending)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users/auth0%7C1/logs",
+    &[("page", "0"), ("per_page", "50"), ("sort", "date:-1")],
+    None,
+  );
+}
+
+#[test]
+fn test_user_enrollments_get() {
+  let req = UserEnrollmentsGet::new(UserId::new("auth0|1")).build(&client());
+  assert_request(&req, Method::GET, "/users/auth0%7C1/enrollments", &[], None);
+}
+
+#[test]
+fn test_user_permissions_get() {
+  let client = client();
+
+  let req = UserPermissionsGet::new(client.clone(), UserId::new("auth0|1"))
+    .page(1)
+    .per_page(5)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/users/auth0%7C1/permissions",
+    &[("page", "1"), ("per_page", "5")],
+    None,
+  );
+}
+
+#[test]
+fn test_user_permissions_update() {
+  let client = client();
+
+  let req = UserPermissionsUpdate::new(client.clone(), UserId::new("auth0|1"))
+    .permission(permission("read:items"))
+    .permissions([permission("write:items")])
+    .build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/users/auth0%7C1/permissions",
+    &[],
+    Some(json!({
+      "permissions": [
+        {
+          "permission_name": "read:items",
+          "description": "",
+          "resource_server_name": "API",
+          "resource_server_identifier": "https://api.example.test",
+        },
+        {
+          "permission_name": "write:items",
+          "description": "",
+          "resource_server_name": "API",
+          "resource_server_identifier": "https://api.example.test",
+        },
+      ]
+    })),
+  );
+}
+
+#[test]
+fn test_user_permissions_delete() {
+  let client = client();
+
+  let req = UserPermissionsDelete::new(client.clone(), UserId::new("auth0|1"))
+    .permission(permission("read:items"))
+    .build(&client);
+  assert_request(
+    &req,
+    Method::DELETE,
+    "/users/auth0%7C1/permissions",
+    &[],
+    Some(json!({
+      "permissions": [{
+        "permission_name": "read:items",
+        "description": "",
+        "resource_server_name": "API",
+        "resource_server_identifier": "https://api.example.test",
+      }]
+    })),
+  );
+}
+
+#[test]
+fn test_user_blocks() {
+  let client = client();
+
+  let req = UserBlocksGet::new(UserId::new("auth0|1")).build(&client);
+  assert_request(&req, Method::GET, "/user-blocks/auth0%7C1", &[], None);
+
+  let req = UserBlocksDelete::new(UserId::new("auth0|1")).build(&client);
+  assert_request(&req, Method::DELETE, "/user-blocks/auth0%7C1", &[], None);
+
+  let req = UserBlocksGetByIdentifier::new("test@example.test").build(&client);
+  assert_request(
+    &req,
+    Method::GET,
+    "/user-blocks",
+    &[("identifier", "test@example.test")],
+    None,
+  );
+
+  let req = UserBlocksDeleteByIdentifier::new("test@example.test").build(&client);
+  assert_request(
+    &req,
+    Method::DELETE,
+    "/user-blocks",
+    &[("identifier", "test@example.test")],
+    None,
+  );
+}
+
+#[test]
+fn test_tickets() {
+  let client = client();
+
+  let req =
+    EmailVerificationTicketCreate::new(client.clone(), UserId::new("google-oauth2|1"))
+      .result_url("https://example.test/verified")
+      .ttl_sec(300)
+      .include_email_in_redirect(true)
+      .identity("1", "google-oauth2")
+      .build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/tickets/email-verification",
+    &[],
+    Some(json!({
+      "user_id": "google-oauth2|1",
+      "result_url": "https://example.test/verified",
+      "ttl_sec": 300,
+      "includeEmailInRedirect": true,
+      "identity": { "user_id": "1", "provider": "google-oauth2" },
+    })),
+  );
+
+  let req = PasswordChangeTicketCreate::new(client.clone())
+    .user_id(UserId::new("auth0|1"))
+    .mark_email_as_verified(true)
+    .build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/tickets/password-change",
+    &[],
+    Some(json!({ "user_id": "auth0|1", "mark_email_as_verified": true })),
+  );
+
+  let req = PasswordChangeTicketCreate::new(client.clone())
+    .email("test@example.test")
+    .connection_id(ConnectionId::new("con_1"))
+    .client_id(ClientId::new("CLIENT_ID"))
+    .organization_id(OrgId::new("org_1"))
+    .build(&client);
+  assert_request(
+    &req,
+    Method::POST,
+    "/tickets/password-change",
+    &[],
+    Some(json!({
+      "email": "test@example.test",
+      "connection_id": "con_1",
+      "client_id": "CLIENT_ID",
+      "organization_id": "org_1",
+    })),
+  );
+}
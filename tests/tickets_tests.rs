@@ -0,0 +1,80 @@
+use serde_json::{json, Value};
+
+use auth0_management::UserId;
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|_| {
+    MockResponse::json(
+      201,
+      json!({ "ticket": "https://example.auth0.com/tickets/TICKET#" }),
+    )
+    .with_rate_limit()
+  })
+}
+
+fn body(server: &MockServer, path: &str) -> Value {
+  let requests = server.requests_to(path);
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0].method, "POST");
+
+  serde_json::from_str(&requests[0].body).unwrap()
+}
+
+#[tokio::test]
+async fn test_email_verification_ticket() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let ticket = auth0
+    .tickets
+    .email_verification(UserId::new("google-oauth2|1"))
+    .result_url("https://example.test/verified")
+    .ttl_sec(300)
+    .include_email_in_redirect(true)
+    .identity("1", "google-oauth2")
+    .await
+    .unwrap();
+  assert_eq!(ticket, "https://example.auth0.com/tickets/TICKET#");
+
+  assert_eq!(
+    body(&server, "/api/v2/tickets/email-verification"),
+    json!({
+      "user_id": "google-oauth2|1",
+      "result_url": "https://example.test/verified",
+      "ttl_sec": 300,
+      "includeEmailInRedirect": true,
+      "identity": { "user_id": "1", "provider": "google-oauth2" },
+    })
+  );
+}
+
+#[tokio::test]
+async fn test_password_change_ticket() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let ticket = auth0
+    .tickets
+    .password_change()
+    .user_id(UserId::new("auth0|1"))
+    .mark_email_as_verified(true)
+    .include_email_in_redirect(false)
+    .await
+    .unwrap();
+  assert_eq!(ticket, "https://example.auth0.com/tickets/TICKET#");
+
+  let body = body(&server, "/api/v2/tickets/password-change");
+  assert_eq!(
+    body,
+    json!({
+      "user_id": "auth0|1",
+      "mark_email_as_verified": true,
+      "includeEmailInRedirect": false,
+    })
+  );
+  assert!(body.get("include_email_in_redirect").is_none());
+}
@@ -0,0 +1,228 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use reqwest::Client;
+use serde_json::json;
+
+use auth0_management::token::{
+  CachedToken, ClientCredentials, FileTokenCache, MemoryTokenCache, TokenCache,
+  TokenCacheKey, TokenError, TokenManager,
+};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn token_server(expires_in: u64) -> MockServer {
+  let issued = AtomicU32::new(0);
+
+  MockServer::start(move |_| {
+    let n = issued.fetch_add(1, Ordering::SeqCst);
+    MockResponse::json(
+      200,
+      json!({ "access_token": format!("TOKEN_{}", n), "expires_in": expires_in }),
+    )
+  })
+}
+
+fn token_manager(
+  server: &MockServer,
+  client_id: &str,
+  cache: Arc<dyn TokenCache>,
+) -> TokenManager {
+  TokenManager::new(
+    Client::new(),
+    &server.domain(),
+    "https://example.auth0.com/api/v2/",
+    client_id,
+    ClientCredentials::Secret("CLIENT_SECRET".to_owned()),
+  )
+  .cache(cache)
+}
+
+#[tokio::test]
+async fn test_file_cache_reused() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("nested").join("tokens.json");
+  let server = token_server(3600);
+
+  // Simulates two process invocations sharing the cache file.
+  let first = token_manager(&server, "CLIENT_ID", Arc::new(FileTokenCache::new(&path)));
+  assert_eq!(first.get_token().await.unwrap(), "TOKEN_0");
+
+  let second = token_manager(&server, "CLIENT_ID", Arc::new(FileTokenCache::new(&path)));
+  assert_eq!(second.get_token().await.unwrap(), "TOKEN_0");
+  assert_eq!(server.requests().len(), 1);
+
+  // Tokens are keyed by client id.
+  let other = token_manager(&server, "OTHER_ID", Arc::new(FileTokenCache::new(&path)));
+  assert_eq!(other.get_token().await.unwrap(), "TOKEN_1");
+  assert_eq!(server.requests().len(), 2);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_file_cache_permissions() {
+  use std::os::unix::fs::PermissionsExt;
+
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("cache").join("tokens.json");
+  let server = token_server(3600);
+
+  token_manager(&server, "CLIENT_ID", Arc::new(FileTokenCache::new(&path)))
+    .get_token()
+    .await
+    .unwrap();
+
+  let file = std::fs::metadata(&path).unwrap().permissions().mode();
+  let parent = std::fs::metadata(path.parent().unwrap())
+    .unwrap()
+    .permissions()
+    .mode();
+
+  assert_eq!(file & 0o777, 0o600);
+  assert_eq!(parent & 0o777, 0o700);
+}
+
+#[tokio::test]
+async fn test_expired_token_ignored() {
+  let dir = tempfile::tempdir().unwrap();
+  let cache = Arc::new(FileTokenCache::new(dir.path().join("tokens.json")));
+  let server = token_server(3600);
+  let manager = token_manager(&server, "CLIENT_ID", cache.clone());
+  let now = SystemTime::now();
+
+  cache
+    .set(
+      &manager.cache_key(),
+      &CachedToken {
+        access_token: "EXPIRED".to_owned(),
+        issued_at: now - Duration::from_secs(7200),
+        expires_at: now - Duration::from_secs(3600),
+      },
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_0");
+  assert_eq!(
+    cache
+      .get(&manager.cache_key())
+      .await
+      .unwrap()
+      .unwrap()
+      .access_token,
+    "TOKEN_0"
+  );
+}
+
+#[tokio::test]
+async fn test_memory_cache_invalidate() {
+  let cache = Arc::new(MemoryTokenCache::new());
+  let server = token_server(3600);
+  let first = token_manager(&server, "CLIENT_ID", cache.clone());
+  let second = token_manager(&server, "CLIENT_ID", cache.clone());
+
+  assert_eq!(first.get_token().await.unwrap(), "TOKEN_0");
+  assert_eq!(second.get_token().await.unwrap(), "TOKEN_0");
+
+  first.invalidate("TOKEN_0").await;
+
+  assert_eq!(cache.get(&first.cache_key()).await.unwrap(), None);
+  assert_eq!(first.get_token().await.unwrap(), "TOKEN_1");
+  assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_file_cache_corrupt() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("tokens.json");
+  let cache = Arc::new(FileTokenCache::new(&path));
+  let server = token_server(3600);
+  let manager = token_manager(&server, "CLIENT_ID", cache.clone());
+
+  std::fs::write(&path, "not json").unwrap();
+  assert!(matches!(
+    cache.get(&manager.cache_key()).await,
+    Err(TokenError::Cache(_))
+  ));
+
+  // The manager falls back to requesting a token and replaces the corrupt file.
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_0");
+  assert_eq!(
+    cache
+      .get(&manager.cache_key())
+      .await
+      .unwrap()
+      .unwrap()
+      .access_token,
+    "TOKEN_0"
+  );
+}
+
+#[test]
+fn test_file_cache_concurrent_writers() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("tokens.json");
+  let now = SystemTime::now();
+
+  // Separate caches don't share a lock, like separate processes.
+  let writers = (0..8)
+    .map(|n| {
+      let cache = FileTokenCache::new(&path);
+      std::thread::spawn(move || {
+        let key = TokenCacheKey {
+          domain: "example.auth0.com".to_owned(),
+          audience: "https://example.auth0.com/api/v2/".to_owned(),
+          client_id: format!("CLIENT_{}", n),
+        };
+        let token = CachedToken {
+          access_token: format!("TOKEN_{}", n),
+          issued_at: now,
+          expires_at: now + Duration::from_secs(3600),
+        };
+
+        for _ in 0..20 {
+          futures::executor::block_on(cache.set(&key, &token)).unwrap();
+        }
+      })
+    })
+    .collect::<Vec<_>>();
+  for writer in writers {
+    writer.join().unwrap();
+  }
+
+  let files = std::fs::read_dir(dir.path()).unwrap().count();
+  let tokens: serde_json::Value =
+    serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+  assert_eq!(files, 1);
+  assert!(tokens.is_object());
+}
+
+#[tokio::test]
+async fn test_file_cache_shared_writers() {
+  let dir = tempfile::tempdir().unwrap();
+  let cache = FileTokenCache::new(dir.path().join("tokens.json"));
+  let now = SystemTime::now();
+
+  let keys = (0..8)
+    .map(|n| TokenCacheKey {
+      domain: "example.auth0.com".to_owned(),
+      audience: "https://example.auth0.com/api/v2/".to_owned(),
+      client_id: format!("CLIENT_{}", n),
+    })
+    .collect::<Vec<_>>();
+  let token = CachedToken {
+    access_token: "TOKEN".to_owned(),
+    issued_at: now,
+    expires_at: now + Duration::from_secs(3600),
+  };
+
+  // Writes on a single threaded executor interleave, the lock keeps every token.
+  futures::future::join_all(keys.iter().map(|key| cache.set(key, &token))).await;
+
+  for key in &keys {
+    assert_eq!(cache.get(key).await.unwrap().unwrap().access_token, "TOKEN");
+  }
+}
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+
+use auth0_management::token::TokenError;
+use auth0_management::{Auth0, StaticToken, User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn user_server() -> MockServer {
+  MockServer::start(|_| {
+    MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+  })
+}
+
+#[tokio::test]
+async fn test_static_token() {
+  let server = user_server();
+  let auth0 = Auth0::builder()
+    .domain(&server.domain())
+    .token_provider(StaticToken::new("STATIC_TOKEN"))
+    .build()
+    .unwrap();
+
+  let user: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(user.user_id, "auth0|1");
+  assert_eq!(requests.len(), 1);
+  assert_eq!(
+    requests[0].header("authorization"),
+    Some("Bearer STATIC_TOKEN")
+  );
+}
+
+#[tokio::test]
+async fn test_closure_provider() {
+  let server = user_server();
+  let calls = Arc::new(AtomicU32::new(0));
+  let counter = calls.clone();
+  let auth0 = Auth0::builder()
+    .domain(&server.domain())
+    .token_provider(move || {
+      let calls = counter.fetch_add(1, Ordering::SeqCst);
+      async move { Ok::<_, TokenError>(format!("TOKEN_{}", calls)) }
+    })
+    .build()
+    .unwrap();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  let requests = server.requests();
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+  assert_eq!(requests[0].header("authorization"), Some("Bearer TOKEN_0"));
+  assert_eq!(requests[1].header("authorization"), Some("Bearer TOKEN_1"));
+}
+
+#[tokio::test]
+async fn test_provider_error() {
+  let auth0 = Auth0::builder()
+    .domain("example.auth0.com")
+    .token_provider(|| async {
+      Err::<String, _>(TokenError::Provider("vault sealed".into()))
+    })
+    .build()
+    .unwrap();
+
+  let res = auth0.users.get(UserId::new("auth0|1")).await;
+  assert!(res.is_err());
+}
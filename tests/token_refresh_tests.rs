@@ -0,0 +1,196 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use futures::future::{join_all, select};
+use futures_timer::Delay;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use auth0_management::token::{ClientCredentials, TokenManager};
+use auth0_management::{Auth0, User, UserId};
+
+use crate::mock::{MockRequest, MockResponse, MockServer};
+
+mod mock;
+
+/// Start server issuing `TOKEN_{n}` tokens valid for `expires_in` seconds.
+fn start(
+  expires_in: u64,
+  api: impl Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+) -> MockServer {
+  let issued = AtomicU32::new(0);
+
+  MockServer::start(move |req| {
+    if req.path != "/oauth/token" {
+      return api(req);
+    }
+
+    // Slow token endpoint so concurrent callers overlap.
+    thread::sleep(Duration::from_millis(100));
+
+    let n = issued.fetch_add(1, Ordering::SeqCst);
+    MockResponse::json(
+      200,
+      json!({ "access_token": format!("TOKEN_{}", n), "expires_in": expires_in }),
+    )
+  })
+}
+
+fn user(_: &MockRequest) -> MockResponse {
+  MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+}
+
+fn token_manager(server: &MockServer) -> TokenManager {
+  TokenManager::new(
+    Client::new(),
+    &server.domain(),
+    "https://example.auth0.com/api/v2/",
+    "CLIENT_ID",
+    ClientCredentials::Secret("CLIENT_SECRET".to_owned()),
+  )
+}
+
+fn auth0(server: &MockServer, leeway: Duration) -> Auth0 {
+  server
+    .builder()
+    .audience("https://example.auth0.com/api/v2/")
+    .token_refresh_leeway(leeway)
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_single_flight() {
+  let server = start(3600, user);
+  let manager = token_manager(&server);
+
+  let tokens = join_all((0..10).map(|_| manager.get_token())).await;
+
+  assert!(tokens
+    .iter()
+    .all(|token| token.as_ref().unwrap() == "TOKEN_0"));
+  assert_eq!(server.requests_to("/oauth/token").len(), 1);
+}
+
+#[tokio::test]
+async fn test_refresh_ahead_of_expiry() {
+  let server = start(4, user);
+  let manager = token_manager(&server).refresh_leeway(Duration::from_secs(3));
+
+  // Leeway is capped at half of the lifetime, the token is refreshed after 2s.
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_0");
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_0");
+
+  Delay::new(Duration::from_millis(2100)).await;
+
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_1");
+}
+
+#[tokio::test]
+async fn test_invalidate() {
+  let server = start(3600, user);
+  let manager = token_manager(&server);
+
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_0");
+
+  // Invalidating a stale token keeps the current one.
+  manager.invalidate("TOKEN_STALE").await;
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_0");
+
+  manager.invalidate("TOKEN_0").await;
+  assert_eq!(manager.get_token().await.unwrap(), "TOKEN_1");
+}
+
+#[tokio::test]
+async fn test_retry_unauthorized() {
+  let server = start(3600, |req| {
+    if req.header("authorization") == Some("Bearer TOKEN_0") {
+      MockResponse::json(401, json!({ "message": "Invalid token" }))
+    } else {
+      user(req)
+    }
+  });
+  let auth0 = auth0(&server, Duration::from_secs(60));
+
+  let user: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  let requests = server.requests_to("/api/v2/users/auth0%7C1");
+  assert_eq!(user.user_id, "auth0|1");
+  assert_eq!(requests.len(), 2);
+  assert_eq!(requests[1].header("authorization"), Some("Bearer TOKEN_1"));
+}
+
+#[tokio::test]
+async fn test_retry_unauthorized_once() {
+  let server = start(3600, |_| {
+    MockResponse::json(401, json!({ "message": "Invalid token" }))
+  });
+  let auth0 = auth0(&server, Duration::from_secs(60));
+
+  let res = auth0.users.get(UserId::new("auth0|1")).await;
+
+  assert!(res.is_err());
+  assert_eq!(server.requests_to("/api/v2/users/auth0%7C1").len(), 2);
+}
+
+#[tokio::test]
+async fn test_background_refresh() {
+  let server = start(2, user);
+  let auth0 = auth0(&server, Duration::from_secs(1));
+
+  // Token is refreshed every second without any request being made.
+  select(
+    Box::pin(auth0.token_refresh_task()),
+    Delay::new(Duration::from_millis(2500)),
+  )
+  .await;
+
+  assert!(server.requests_to("/oauth/token").len() >= 3);
+  assert!(server.requests_to("/api/v2/users/auth0%7C1").is_empty());
+}
+
+#[tokio::test]
+async fn test_background_refresh_doesnt_block_requests() {
+  let issued = Arc::new(AtomicU32::new(0));
+  let server = MockServer::start({
+    let issued = issued.clone();
+    move |req| {
+      if req.path != "/oauth/token" {
+        return user(req);
+      }
+
+      // Refreshes take long enough for a request to arrive while one is in progress.
+      let n = issued.fetch_add(1, Ordering::SeqCst);
+      if n > 0 {
+        thread::sleep(Duration::from_millis(1500));
+      }
+      MockResponse::json(
+        200,
+        json!({ "access_token": format!("TOKEN_{}", n), "expires_in": 4 }),
+      )
+    }
+  });
+  let auth0 = auth0(&server, Duration::from_secs(1));
+  let task = tokio::spawn(auth0.token_refresh_task());
+
+  // TOKEN_0 is refreshed in the background 2s after it was issued, requests would refresh
+  // it after 3s.  The refresh is in progress from 2s to 3.5s.
+  Delay::new(Duration::from_millis(2500)).await;
+  assert_eq!(issued.load(Ordering::SeqCst), 2);
+  assert_eq!(server.requests_to("/oauth/token").len(), 1);
+
+  let start = Instant::now();
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+  assert!(start.elapsed() < Duration::from_millis(500));
+
+  Delay::new(Duration::from_millis(1500)).await;
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+  task.abort();
+
+  let requests = server.requests_to("/api/v2/users/auth0%7C1");
+  assert_eq!(requests[0].header("authorization"), Some("Bearer TOKEN_0"));
+  assert_eq!(requests[1].header("authorization"), Some("Bearer TOKEN_1"));
+  assert_eq!(server.requests_to("/oauth/token").len(), 2);
+}
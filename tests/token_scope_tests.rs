@@ -0,0 +1,142 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use jsonwebtoken::{EncodingKey, Header};
+use serde_json::{json, Value};
+
+use auth0_management::{Auth0, Auth0Error, StaticToken, TokenInfo, User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn jwt(claims: Value) -> String {
+  jsonwebtoken::encode(
+    &Header::default(),
+    &claims,
+    &EncodingKey::from_secret(b"secret"),
+  )
+  .unwrap()
+}
+
+fn user_server() -> MockServer {
+  MockServer::start(|_| {
+    MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+  })
+}
+
+fn auth0(server: &MockServer, token: &str) -> Auth0 {
+  Auth0::builder()
+    .domain(&server.domain())
+    .token_provider(StaticToken::new(token))
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_decode() {
+  let info = TokenInfo::decode(&jwt(json!({
+    "sub": "client@clients",
+    "iat": 1600000000,
+    "exp": 1600086400,
+    "scope": "read:users update:users",
+  })))
+  .unwrap();
+
+  assert_eq!(info.scopes, vec!["read:users", "update:users"]);
+  assert_eq!(
+    info.expires_at,
+    Some(UNIX_EPOCH + Duration::from_secs(1600086400))
+  );
+  assert_eq!(info.claims["sub"], "client@clients");
+  assert!(info.has_scope("update:users"));
+  assert!(!info.has_scope("delete:users"));
+  assert!(info.is_expired());
+}
+
+#[test]
+fn test_decode_opaque() {
+  assert!(TokenInfo::decode("opaque").is_err());
+  assert!(TokenInfo::decode("a.b.c").is_err());
+}
+
+#[tokio::test]
+async fn test_token_info_from_token_endpoint() {
+  let token = jwt(json!({ "exp": 4102444800u64, "scope": "read:users" }));
+  let server = MockServer::start(move |_| {
+    MockResponse::json(
+      200,
+      json!({
+        "access_token": token,
+        "scope": "read:users",
+        "token_type": "Bearer",
+        "expires_in": 86400
+      }),
+    )
+  });
+  let auth0 = server
+    .builder()
+    .audience("https://example.auth0.com/api/v2/")
+    .build()
+    .unwrap();
+
+  let info = auth0.token_info().await.unwrap();
+
+  assert_eq!(info.scopes, vec!["read:users"]);
+  assert!(!info.is_expired());
+}
+
+#[tokio::test]
+async fn test_missing_scope_fails_fast() {
+  let server = user_server();
+  let auth0 = auth0(&server, &jwt(json!({ "scope": "read:users" })));
+
+  match auth0.users.delete(UserId::new("auth0|1")).await {
+    Err(Auth0Error::MissingScope(scopes)) => assert_eq!(scopes, vec!["delete:users"]),
+    res => panic!("expected missing scope, got {:?}", res),
+  }
+
+  match auth0
+    .users
+    .update(UserId::new("auth0|1"))
+    .email("a@b.c")
+    .send::<Value, Value>()
+    .await
+  {
+    Err(Auth0Error::MissingScope(scopes)) => {
+      assert_eq!(scopes, vec!["update:users", "update:users_app_metadata"])
+    }
+    res => panic!("expected missing scope, got {:?}", res),
+  }
+
+  assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_granted_scope_is_sent() {
+  let server = user_server();
+  let auth0 = auth0(
+    &server,
+    &jwt(json!({ "scope": "update:users_app_metadata read:users" })),
+  );
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+  let _: User<Value, Value> = auth0
+    .users
+    .update(UserId::new("auth0|1"))
+    .app_metadata(json!({ "plan": "pro" }))
+    .send()
+    .await
+    .unwrap();
+
+  assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_opaque_token_is_sent() {
+  let server = user_server();
+  let auth0 = auth0(&server, "OPAQUE_TOKEN");
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("auth0|1")).await.unwrap();
+
+  assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,219 @@
+#![cfg(feature = "tracing")]
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+use auth0_management::{User, UserId};
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+type Fields = HashMap<String, String>;
+
+/// Subscriber recording the fields of every span and event.
+#[derive(Clone, Default)]
+struct Recorder {
+  next: Arc<AtomicU64>,
+  spans: Arc<Mutex<HashMap<u64, (&'static Metadata<'static>, Fields)>>>,
+  events: Arc<Mutex<Vec<Fields>>>,
+  stack: Arc<Mutex<Vec<u64>>>,
+}
+
+struct Visitor<'a>(&'a mut Fields);
+
+impl<'a> Visit for Visitor<'a> {
+  fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+    self
+      .0
+      .insert(field.name().to_owned(), format!("{:?}", value));
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.0.insert(field.name().to_owned(), value.to_owned());
+  }
+}
+
+impl Subscriber for Recorder {
+  fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+    metadata.target().starts_with("auth0_management")
+  }
+
+  fn new_span(&self, span: &Attributes<'_>) -> Id {
+    let id = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+    let mut fields = Fields::new();
+    span.record(&mut Visitor(&mut fields));
+
+    self
+      .spans
+      .lock()
+      .unwrap()
+      .insert(id, (span.metadata(), fields));
+
+    Id::from_u64(id)
+  }
+
+  fn record(&self, span: &Id, values: &Record<'_>) {
+    let mut spans = self.spans.lock().unwrap();
+    let (_, fields) = spans.get_mut(&span.into_u64()).unwrap();
+
+    values.record(&mut Visitor(fields));
+  }
+
+  fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+  fn event(&self, event: &Event<'_>) {
+    let mut fields = Fields::new();
+    fields.insert("level".to_owned(), event.metadata().level().to_string());
+    event.record(&mut Visitor(&mut fields));
+
+    self.events.lock().unwrap().push(fields);
+  }
+
+  fn enter(&self, span: &Id) {
+    self.stack.lock().unwrap().push(span.into_u64());
+  }
+
+  fn exit(&self, _: &Id) {
+    self.stack.lock().unwrap().pop();
+  }
+
+  fn current_span(&self) -> Current {
+    let spans = self.spans.lock().unwrap();
+
+    match self.stack.lock().unwrap().last() {
+      Some(id) => Current::new(Id::from_u64(*id), spans[id].0),
+      None => Current::none(),
+    }
+  }
+}
+
+impl Recorder {
+  fn spans(&self, name: &str) -> Vec<Fields> {
+    let mut spans = self
+      .spans
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(_, (span, _))| span.name() == name)
+      .map(|(id, (_, fields))| (*id, fields.clone()))
+      .collect::<Vec<_>>();
+
+    spans.sort_by_key(|(id, _)| *id);
+    spans.into_iter().map(|(_, fields)| fields).collect()
+  }
+
+  fn messages(&self) -> Vec<String> {
+    self
+      .events
+      .lock()
+      .unwrap()
+      .iter()
+      .filter_map(|event| event.get("message").cloned())
+      .collect()
+  }
+}
+
+fn tenant_server() -> MockServer {
+  let tokens = AtomicU32::new(0);
+
+  MockServer::start(move |req| {
+    if req.path == "/oauth/token" {
+      let n = tokens.fetch_add(1, Ordering::SeqCst);
+      return MockResponse::token(&format!("SECRET_TOKEN_{}", n));
+    }
+    if req.path.starts_with("/api/v2/users/throttled") {
+      return MockResponse::json(429, json!({ "message": "Too Many Requests" }))
+        .header("x-ratelimit-limit", "50")
+        .header("x-ratelimit-remaining", "0")
+        .header("x-ratelimit-reset", "1600000000");
+    }
+    if req.path.starts_with("/api/v2/users/rejected")
+      && req.header("authorization") == Some("Bearer SECRET_TOKEN_0")
+    {
+      return MockResponse::json(401, json!({ "message": "Unauthorized" }));
+    }
+
+    MockResponse::json(200, json!({ "user_id": "auth0|1" })).with_rate_limit()
+  })
+}
+
+#[tokio::test]
+async fn test_request_spans() {
+  let recorder = Recorder::default();
+  let _guard = tracing::subscriber::set_default(recorder.clone());
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("sensitive-id")).await.unwrap();
+
+  let requests = recorder.spans("auth0.request");
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0]["method"], "GET");
+  assert_eq!(requests[0]["path"], "api/v2/users/{id}");
+  assert_eq!(requests[0]["status"], "200");
+  assert_eq!(requests[0]["attempt"], "1");
+  assert_eq!(requests[0]["rate_limit_remaining"], "49");
+
+  let tokens = recorder.spans("auth0.token");
+  assert_eq!(tokens.len(), 1);
+  assert_eq!(tokens[0]["method"], "POST");
+  assert_eq!(tokens[0]["status"], "200");
+  assert!(recorder
+    .messages()
+    .contains(&"access token refreshed".to_owned()));
+}
+
+#[tokio::test]
+async fn test_retry_and_throttle_events() {
+  let recorder = Recorder::default();
+  let _guard = tracing::subscriber::set_default(recorder.clone());
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("rejected")).await.unwrap();
+  assert!(auth0.users.get(UserId::new("throttled")).await.is_err());
+
+  let requests = recorder.spans("auth0.request");
+  assert_eq!(requests[0]["attempt"], "2");
+  assert_eq!(requests[1]["status"], "429");
+  assert_eq!(requests[1]["rate_limit_remaining"], "0");
+
+  let messages = recorder.messages();
+  assert!(
+    messages.contains(&"access token rejected, retrying with a new token".to_owned())
+  );
+  assert!(messages.contains(&"auth0 rate limit exceeded".to_owned()));
+}
+
+#[tokio::test]
+async fn test_secrets_redacted() {
+  let recorder = Recorder::default();
+  let _guard = tracing::subscriber::set_default(recorder.clone());
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let _: User<Value, Value> = auth0.users.get(UserId::new("rejected")).await.unwrap();
+
+  let spans = recorder.spans.lock().unwrap().clone();
+  let events = recorder.events.lock().unwrap().clone();
+  let values = spans
+    .values()
+    .flat_map(|(_, fields)| fields.values().cloned())
+    .chain(events.iter().flat_map(|fields| fields.values().cloned()))
+    .collect::<Vec<_>>();
+
+  assert!(!values.is_empty());
+  for value in values {
+    assert!(!value.contains("SECRET_TOKEN"), "{}", value);
+    assert!(!value.contains("CLIENT_SECRET"), "{}", value);
+    assert!(!value.contains("users/rejected"), "{}", value);
+  }
+}
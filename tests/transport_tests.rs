@@ -0,0 +1,118 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde_json::{json, Value};
+
+use auth0_management::rate::RateLimit;
+use auth0_management::{
+  Auth0, Auth0Client, Auth0Error, Auth0RequestBuilder, Configurable, HttpBody, HttpError,
+  HttpRequest, HttpResponse, HttpTransport, Pageable, StaticToken, User, UserGet, UserId,
+};
+
+/// Transport recording requests and answering them without a network.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<HttpRequest>>>);
+
+#[async_trait]
+impl HttpTransport for Recorder {
+  async fn send(&self, req: HttpRequest) -> Result<HttpResponse, HttpError> {
+    let body = if req.url.ends_with("/oauth/token") {
+      json!({ "access_token": "TOKEN", "expires_in": 3600 })
+    } else if req.url.ends_with("/api/v2/users") {
+      json!([{ "user_id": "auth0|1" }])
+    } else {
+      json!({ "user_id": "auth0|1" })
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert("x-ratelimit-limit", 50.into());
+    headers.insert("x-ratelimit-remaining", 49.into());
+    headers.insert("x-ratelimit-reset", 0.into());
+
+    self.0.lock().unwrap().push(req);
+
+    Ok(HttpResponse {
+      status: StatusCode::OK,
+      headers,
+      body: serde_json::to_vec(&body).unwrap(),
+    })
+  }
+}
+
+struct Offline;
+
+#[async_trait]
+impl HttpTransport for Offline {
+  async fn send(&self, _: HttpRequest) -> Result<HttpResponse, HttpError> {
+    Err(HttpError::connect("offline"))
+  }
+}
+
+#[tokio::test]
+async fn test_custom_transport() {
+  let transport = Recorder::default();
+  let auth0 = Auth0::builder()
+    .domain("example.auth0.com")
+    .client_id("CLIENT_ID")
+    .client_secret("CLIENT_SECRET")
+    .transport(transport.clone())
+    .build()
+    .unwrap();
+
+  let users: Vec<User<Value, Value>> =
+    auth0.users.find().per_page(2).send().await.unwrap();
+  assert_eq!(users[0].user_id, "auth0|1");
+
+  let requests = transport.0.lock().unwrap().clone();
+  assert_eq!(requests.len(), 2);
+
+  let token = &requests[0];
+  assert_eq!(token.method, Method::POST);
+  assert_eq!(token.url, "https://example.auth0.com/oauth/token");
+  match &token.body {
+    Some(HttpBody::Form(form)) => {
+      assert!(form.contains(&("grant_type".to_owned(), "client_credentials".to_owned())))
+    }
+    body => panic!("unexpected token body {:?}", body),
+  }
+
+  let find = &requests[1];
+  assert_eq!(find.method, Method::GET);
+  assert_eq!(find.url, "https://example.auth0.com/api/v2/users");
+  assert_eq!(find.query, vec![("per_page".to_owned(), "2".to_owned())]);
+  assert_eq!(find.headers["authorization"], "Bearer TOKEN");
+}
+
+#[tokio::test]
+async fn test_transport_errors() {
+  let auth0 = Auth0::builder()
+    .domain("example.auth0.com")
+    .token_provider(StaticToken::new("TOKEN"))
+    .transport(Offline)
+    .build()
+    .unwrap();
+
+  let res = auth0.users.get(UserId::new("1")).await;
+  assert!(matches!(res, Err(Auth0Error::Http(err)) if err.is_connect()));
+}
+
+#[test]
+fn test_build_request() {
+  let client = Auth0Client::with_transport(
+    RateLimit::new(),
+    Arc::new(StaticToken::new("TOKEN")),
+    Arc::new(Offline),
+    "example.auth0.com",
+  );
+  let mut get = UserGet::new(UserId::new("1"));
+  get.timeout(Duration::from_secs(5));
+
+  let req = get.build(&client);
+  assert_eq!(req.method, Method::GET);
+  assert_eq!(req.url, "https://example.auth0.com/api/v2/users/1");
+  assert!(req.query.is_empty());
+  assert_eq!(req.body, None);
+  assert_eq!(req.timeout, Some(Duration::from_secs(5)));
+}
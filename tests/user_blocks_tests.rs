@@ -0,0 +1,78 @@
+use serde_json::json;
+
+use auth0_management::UserId;
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    let body = if req.method == "DELETE" {
+      json!(null)
+    } else {
+      json!({
+        "blocked_for": [
+          { "identifier": "test@example.test", "ip": "10.0.0.1" },
+          { "identifier": "test@example.test" },
+        ]
+      })
+    };
+
+    MockResponse::json(200, body).with_rate_limit()
+  })
+}
+
+#[tokio::test]
+async fn test_get_blocks() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let blocks = auth0.user_blocks.get(UserId::new("auth0|1")).await.unwrap();
+  assert_eq!(blocks.len(), 2);
+  assert_eq!(blocks[0].identifier, "test@example.test");
+  assert_eq!(blocks[0].ip.as_deref(), Some("10.0.0.1"));
+  assert_eq!(blocks[1].ip, None);
+
+  let blocks = auth0
+    .user_blocks
+    .get_by_identifier("test@example.test")
+    .await
+    .unwrap();
+  assert_eq!(blocks.len(), 2);
+
+  let requests = server.requests();
+  assert_eq!(requests[1].method, "GET");
+  assert_eq!(requests[1].path, "/api/v2/user-blocks/auth0%7C1");
+  assert_eq!(requests[2].method, "GET");
+  assert_eq!(
+    requests[2].path,
+    "/api/v2/user-blocks?identifier=test%40example.test"
+  );
+}
+
+#[tokio::test]
+async fn test_delete_blocks() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  auth0
+    .user_blocks
+    .delete(UserId::new("auth0|1"))
+    .await
+    .unwrap();
+  auth0
+    .user_blocks
+    .delete_by_identifier("test@example.test")
+    .await
+    .unwrap();
+
+  let requests = server.requests();
+  assert_eq!(requests[1].method, "DELETE");
+  assert_eq!(requests[1].path, "/api/v2/user-blocks/auth0%7C1");
+  assert_eq!(requests[2].method, "DELETE");
+  assert_eq!(
+    requests[2].path,
+    "/api/v2/user-blocks?identifier=test%40example.test"
+  );
+}
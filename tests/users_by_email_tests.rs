@@ -0,0 +1,82 @@
+use serde_json::{json, Value};
+
+use auth0_management::User;
+
+use crate::mock::{MockResponse, MockServer};
+
+mod mock;
+
+fn tenant_server() -> MockServer {
+  MockServer::tenant(|req| {
+    let body = if req.path.contains("email=test%40example.test") {
+      json!([{ "user_id": "auth0|1" }, { "user_id": "oidc|2" }])
+    } else {
+      json!([{ "user_id": "oidc|2" }])
+    };
+
+    MockResponse::json(200, body).with_rate_limit()
+  })
+}
+
+fn lookups(server: &MockServer) -> Vec<String> {
+  server
+    .requests()
+    .into_iter()
+    .map(|req| req.path)
+    .filter(|path| path.starts_with("/api/v2/users-by-email"))
+    .collect()
+}
+
+fn user_ids(users: &[User<Value, Value>]) -> Vec<&str> {
+  users.iter().map(|user| user.user_id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_find_by_email_case_sensitive() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let users = auth0
+    .users
+    .find_by_email("Test@Example.test")
+    .await
+    .unwrap();
+  assert_eq!(user_ids(&users), ["oidc|2"]);
+  assert_eq!(lookups(&server).len(), 1);
+}
+
+#[tokio::test]
+async fn test_find_by_email_case_insensitive() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let users = auth0
+    .users
+    .find_by_email("Test@Example.test")
+    .case_insensitive(true)
+    .await
+    .unwrap();
+  assert_eq!(user_ids(&users), ["oidc|2", "auth0|1"]);
+  assert_eq!(
+    lookups(&server),
+    [
+      "/api/v2/users-by-email?email=Test%40Example.test",
+      "/api/v2/users-by-email?email=test%40example.test",
+    ]
+  );
+}
+
+#[tokio::test]
+async fn test_find_by_email_case_insensitive_lowercase() {
+  let server = tenant_server();
+  let auth0 = server.auth0();
+
+  let users = auth0
+    .users
+    .find_by_email("test@example.test")
+    .case_insensitive(true)
+    .await
+    .unwrap();
+  assert_eq!(user_ids(&users), ["auth0|1", "oidc|2"]);
+  assert_eq!(lookups(&server).len(), 1);
+}
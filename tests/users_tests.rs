@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use auth0_management::{Ordering, Pageable, Sortable};
+use auth0_management::{Ordering, Pageable, Sortable, UserSortField};
 
 use crate::helpers::get_client;
 
@@ -29,7 +29,7 @@ async fn test_find_user() {
     .users
     .find()
     .page(0)
-    .sort("email", Ordering::Ascending)
+    .sort(UserSortField::Email, Ordering::Ascending)
     .send::<Metadata, Metadata>()
     .await
     .expect("Failed to fetch users.");